@@ -36,9 +36,28 @@ fn main() {
     println!("Solved 2 in {:?}", start.elapsed());
 }
 
+/// Simulates every fish on its own, only feasible for a small number of days
+#[cfg(test)]
+mod reference {
+    pub fn count_fishes(timers: &[u64], days: u64) -> u64 {
+        let mut timers = timers.to_vec();
+
+        for _ in 0..days {
+            let new_fishes = timers.iter().filter(|timer| **timer == 0).count();
+            for timer in timers.iter_mut() {
+                *timer = if *timer == 0 { 6 } else { *timer - 1 };
+            }
+            timers.extend(std::iter::repeat_n(8, new_fishes));
+        }
+
+        timers.len() as u64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::random::Rng;
 
     const EXAMPLE: &str = include_str!("./example.txt");
 
@@ -51,4 +70,23 @@ mod tests {
     fn test2() {
         assert_eq!(solve(EXAMPLE, 256), 26984457539);
     }
+
+    #[test]
+    fn matches_reference() {
+        let mut rng = Rng::new(6);
+
+        for _ in 0..100 {
+            let timers: Vec<u64> = (0..rng.range(1..10))
+                .map(|_| rng.range(0..9) as u64)
+                .collect();
+            let days = rng.range(0..60) as u64;
+
+            let input: Vec<String> = timers.iter().map(|timer| timer.to_string()).collect();
+            assert_eq!(
+                solve(&input.join(","), days),
+                reference::count_fishes(&timers, days),
+                "{timers:?} {days}"
+            );
+        }
+    }
 }
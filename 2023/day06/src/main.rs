@@ -4,27 +4,29 @@ use std::time::Instant;
 const EXAMPLE_FILENAME: &str = "./src/example.txt";
 const PUZZLE_FILENAME: &str = "./src/puzzle.txt";
 
-// fn calculate_distances(time: u64, min_distance: u64) -> u64 {
-//     return (0..=time)
-//         .map(|i| (time - i) * i)
-//         .filter(|x| *x > min_distance)
-//         .count() as u64;
-// }
-
+/// Counts the button presses that beat the record. The roots of the quadratic only give an
+/// estimate, which is corrected with exact integer checks so large races don't lose precision.
 fn calculate_distances_math(time: u64, min_distance: u64) -> u64 {
-    let a = -1f64;
-    let b = time as f64;
-    let c = -(min_distance as f64 + 0.001);
-
-    let d = b.powf(2.0) - (4.0 * a * c);
-
-    let s1 = (-b + d.sqrt()) / (2.0 * a);
-    let s2 = (-b - d.sqrt()) / (2.0 * a);
-
-    let s1 = s1.ceil();
-    let s2 = s2.floor();
-
-    (s2 - s1) as u64 + 1
+    let beats = |press: u64| press * (time - press) > min_distance;
+
+    // The distance peaks in the middle, if that can't beat the record nothing can
+    if !beats(time / 2) {
+        return 0;
+    }
+
+    let t = time as f64;
+    let d = t * t - 4.0 * min_distance as f64;
+    let mut low = ((t - d.max(0.0).sqrt()) / 2.0).clamp(0.0, (time / 2) as f64) as u64;
+
+    while low > 0 && beats(low - 1) {
+        low -= 1;
+    }
+    while !beats(low) {
+        low += 1;
+    }
+
+    // The distances are symmetric around the middle
+    time - 2 * low + 1
 }
 
 fn solve1(filename: &str) {
@@ -90,3 +92,44 @@ fn main() {
     solve2(PUZZLE_FILENAME);
     println!("Solved 2 in {:?}", start.elapsed());
 }
+
+/// Tries every possible button press
+#[cfg(test)]
+mod reference {
+    pub fn calculate_distances(time: u64, min_distance: u64) -> u64 {
+        (0..=time)
+            .map(|i| (time - i) * i)
+            .filter(|x| *x > min_distance)
+            .count() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::random::Rng;
+
+    #[test]
+    fn matches_reference() {
+        let mut rng = Rng::new(6);
+
+        for _ in 0..2000 {
+            let time = rng.range(0..200) as u64;
+            // Includes records that can't be beaten and records that are hit exactly
+            let min_distance = match rng.range(0..3) {
+                0 => rng.range(0..(time * time / 4 + 10) as i64) as u64,
+                1 => {
+                    let press = rng.range(0..time as i64 + 1) as u64;
+                    press * (time - press)
+                }
+                _ => time * time / 4 + rng.range(0..3) as u64,
+            };
+
+            assert_eq!(
+                calculate_distances_math(time, min_distance),
+                reference::calculate_distances(time, min_distance),
+                "{time} {min_distance}"
+            );
+        }
+    }
+}
@@ -1,49 +1,43 @@
-use std::collections::HashMap;
 use std::fs;
 use std::str::{Chars, Lines};
 use std::time::Instant;
 
+use graph::{Graph, NodeId};
 use utils::least_common_multiple;
 
 const PUZZLE_FILENAME: &str = "./src/puzzle.txt";
 
-fn calculate_steps(
-    map: &HashMap<String, (String, String)>,
-    directions: Chars,
-    start: String,
-    end: fn(&String) -> bool,
-) -> u64 {
+fn calculate_steps(map: &Graph, directions: Chars, start: NodeId, end: fn(&str) -> bool) -> u64 {
     let mut pos = start;
-    let step = |cur_pos: &String, dir: char| -> String {
-        let item = map.get(cur_pos.as_str()).unwrap();
+    let step = |cur_pos: NodeId, dir: char| -> NodeId {
+        let [left, right] = map.neighbors(cur_pos) else {
+            panic!("Every node should have a left and right edge");
+        };
         if dir == 'L' {
-            item.0.clone()
+            left.to
         } else {
-            item.1.clone()
+            right.to
         }
     };
 
     let steps = directions
         .cycle()
         .take_while(|dir| {
-            pos = step(&pos, *dir);
-            !end(&pos)
+            pos = step(pos, *dir);
+            !end(map.name(pos))
         })
         .count();
 
     steps as u64 + 1
 }
 
-fn generate_map(lines: Lines) -> HashMap<String, (String, String)> {
-    lines
-        .skip(2)
-        .map(|line| {
-            (
-                line[0..3].to_string(),
-                (line[7..10].to_string(), line[12..15].to_string()),
-            )
-        })
-        .collect()
+fn generate_map(lines: Lines) -> Graph {
+    let mut map = Graph::new();
+    for line in lines.skip(2) {
+        map.add_edge(&line[0..3], &line[7..10], 1);
+        map.add_edge(&line[0..3], &line[12..15], 1);
+    }
+    map
 }
 
 fn solve1(filename: &str) -> u64 {
@@ -54,9 +48,8 @@ fn solve1(filename: &str) -> u64 {
     let map = generate_map(lines.clone());
     let directions = lines.clone().next().unwrap().chars();
 
-    let steps = calculate_steps(&map, directions, "AAA".to_string(), |pos: &String| {
-        pos.eq("ZZZ")
-    });
+    let start = map.id("AAA").unwrap();
+    let steps = calculate_steps(&map, directions, start, |pos| pos == "ZZZ");
 
     // println!("Map: {:?}", map);
     println!("Steps: {steps}");
@@ -71,17 +64,14 @@ fn solve2(filename: &str) -> u64 {
     let map = generate_map(lines.clone());
     let directions = lines.clone().next().unwrap().chars();
 
-    let positions: Vec<&str> = map
-        .iter()
-        .map(|(start, (_, _))| start.as_str())
-        .filter(|start| start.ends_with("A"))
+    let positions: Vec<NodeId> = map
+        .nodes()
+        .filter(|start| map.name(*start).ends_with("A"))
         .collect();
 
-    let results = positions.iter().map(|pos| {
-        calculate_steps(&map, directions.clone(), pos.to_string(), |pos: &String| {
-            pos.ends_with("Z")
-        })
-    });
+    let results = positions
+        .iter()
+        .map(|pos| calculate_steps(&map, directions.clone(), *pos, |pos| pos.ends_with("Z")));
 
     let steps = results.fold(1, least_common_multiple);
 
@@ -104,18 +94,8 @@ fn main() {
 mod tests {
     use super::*;
 
-    const EXAMPLE_FILENAME: &str = "./src/example.txt";
-    const EXAMPLE2_FILENAME: &str = "./src/example2.txt";
-
-    #[test]
-    fn test1() {
-        let result = solve1(EXAMPLE_FILENAME);
-        assert_eq!(result, 6);
-    }
-
-    #[test]
-    fn test2() {
-        let result = solve2(EXAMPLE2_FILENAME);
-        assert_eq!(result, 6);
+    example_tests::example_tests! {
+        part1 = |example| solve1(example.path);
+        part2 = |example| solve2(example.path);
     }
 }
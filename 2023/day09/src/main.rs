@@ -1,35 +1,7 @@
 use std::fs;
 use std::time::Instant;
 
-fn calculate_differences(input: &Vec<i64>) -> Vec<i64> {
-    input
-        .as_slice()
-        .windows(2)
-        .map(|val| val[1] - val[0])
-        .collect()
-}
-
-fn generate_next(input: &Vec<i64>) -> i64 {
-    let differences = calculate_differences(input);
-    let retval = if differences.iter().all(|x| *x == 0) {
-        *input.last().unwrap()
-    } else {
-        input.last().unwrap() + generate_next(&differences)
-    };
-    // println!("Next: {retval}");
-    retval
-}
-
-fn generate_previous(input: &Vec<i64>) -> i64 {
-    let differences = calculate_differences(input);
-    let retval = if differences.iter().all(|x| *x == 0) {
-        *input.first().unwrap()
-    } else {
-        input.first().unwrap() - generate_previous(&differences)
-    };
-    // println!("Next: {retval}");
-    retval
-}
+use utils::sequence::extrapolate;
 
 fn common(filename: &str) -> Vec<Vec<i64>> {
     println!("Solving for file: {filename}");
@@ -49,13 +21,19 @@ fn common(filename: &str) -> Vec<Vec<i64>> {
 fn solve1(filename: &str) -> i64 {
     let input = common(filename);
 
-    input.iter().map(generate_next).sum()
+    input
+        .iter()
+        .map(|values| extrapolate(values, values.len() as i64) as i64)
+        .sum()
 }
 
 fn solve2(filename: &str) -> i64 {
     let input = common(filename);
 
-    input.iter().map(generate_previous).sum()
+    input
+        .iter()
+        .map(|values| extrapolate(values, -1) as i64)
+        .sum()
 }
 
 const PUZZLE_FILENAME: &str = "./src/puzzle.txt";
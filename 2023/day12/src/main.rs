@@ -1,8 +1,8 @@
-use std::collections::hash_map::Entry::Occupied;
-use std::collections::HashMap;
 use std::fs;
 use std::time::Instant;
 
+use utils::memo::Memo;
+
 fn parse_sizes(sizes: &str) -> Vec<usize> {
     sizes
         .split(',')
@@ -20,19 +20,26 @@ fn parse_line(line: &str) -> (&str, Vec<usize>) {
     (record, parse_sizes(sizes))
 }
 
-type State = (String, Vec<usize>, Option<usize>);
+// Records and sizes are always suffixes of the original input so their lengths identify them
+type State = (usize, usize, Option<usize>);
 
 fn calculate_arrangements(
     records: &str,
     sizes: &[usize],
     active: Option<usize>,
-    memo: &mut HashMap<State, u64>,
+    memo: &mut Memo<State, u64>,
 ) -> u64 {
-    let state: State = (records.to_owned(), sizes.to_vec(), active);
-    if let Occupied(items) = memo.entry(state.clone()) {
-        return *items.get();
-    }
+    memo.get_or_compute((records.len(), sizes.len(), active), |memo| {
+        count_arrangements(records, sizes, active, memo)
+    })
+}
 
+fn count_arrangements(
+    records: &str,
+    sizes: &[usize],
+    active: Option<usize>,
+    memo: &mut Memo<State, u64>,
+) -> u64 {
     if (active.is_none() || active == Some(0)) && sizes.is_empty() {
         if records.chars().any(|ch| ch == '#') {
             return 0;
@@ -49,7 +56,7 @@ fn calculate_arrangements(
         .expect("Should be at least 1 character here");
     let records = &records[1..];
 
-    let value = if let Some(remainder) = active {
+    if let Some(remainder) = active {
         if remainder == 0 {
             match record {
                 '#' => 0,
@@ -71,10 +78,7 @@ fn calculate_arrangements(
             }
             _ => 0,
         }
-    };
-    memo.insert(state, value);
-
-    value
+    }
 }
 
 fn unfold(records: &str, sizes: &[usize]) -> (String, Vec<usize>) {
@@ -92,7 +96,7 @@ fn solve1(filename: &str) -> u64 {
 
     lines
         .map(parse_line)
-        .map(|(records, sizes)| calculate_arrangements(records, &sizes, None, &mut HashMap::new()))
+        .map(|(records, sizes)| calculate_arrangements(records, &sizes, None, &mut Memo::new()))
         .sum()
 }
 
@@ -106,7 +110,7 @@ fn solve2(filename: &str) -> u64 {
         .map(parse_line)
         .map(|(records, sizes)| {
             let (records, sizes) = unfold(records, &sizes);
-            calculate_arrangements(&records, &sizes, None, &mut HashMap::new())
+            calculate_arrangements(&records, &sizes, None, &mut Memo::new())
         })
         .sum()
 }
@@ -130,7 +134,7 @@ mod tests {
     #[test]
     fn test_calculate_arrangements() {
         assert_eq!(
-            calculate_arrangements("???", &[1], None, &mut HashMap::new()),
+            calculate_arrangements("???", &[1], None, &mut Memo::new()),
             3
         );
     }
@@ -138,7 +142,7 @@ mod tests {
     #[test]
     fn test_calculate_arrangements2() {
         assert_eq!(
-            calculate_arrangements("???", &[1, 1], None, &mut HashMap::new()),
+            calculate_arrangements("???", &[1, 1], None, &mut Memo::new()),
             1
         );
     }
@@ -147,7 +151,7 @@ mod tests {
     fn test_first_string() {
         let (records, sizes) = parse_line("?????????#?#.#?.?.# 4,3,1,1,1");
         assert_eq!(
-            calculate_arrangements(records, &sizes, None, &mut HashMap::new()),
+            calculate_arrangements(records, &sizes, None, &mut Memo::new()),
             8
         );
     }
@@ -157,7 +161,7 @@ mod tests {
         let (records, sizes) = parse_line("?###???????? 3,2,1");
         let (records, sizes) = unfold(records, &sizes);
         assert_eq!(
-            calculate_arrangements(&records, &sizes, None, &mut HashMap::new()),
+            calculate_arrangements(&records, &sizes, None, &mut Memo::new()),
             506250
         );
     }
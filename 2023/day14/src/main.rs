@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::time::Instant;
 
+use grid::{image::Rgb, terminal::Terminal, Grid};
+
 fn transpose(input: &[String]) -> Vec<String> {
     let len = input[0].len();
     input
@@ -62,9 +64,16 @@ fn calculate_load(input: &[String]) -> u64 {
 fn print_grid(input: &[String]) {
     println!("Grid:");
 
-    for line in transpose(input) {
-        println!("{line}");
-    }
+    // The lines are stored as columns, so the text is transposed back when drawing
+    let grid = Grid::from_text(&input.join("\n")).transpose();
+    Terminal::stdout()
+        .palette(|ch| match ch {
+            'O' => Some(Rgb(255, 200, 0)),
+            '#' => Some(Rgb(128, 128, 128)),
+            _ => None,
+        })
+        .draw(&grid, &HashSet::new())
+        .expect("Grid to be printed");
 }
 
 fn solve1(filename: &str) -> u64 {
@@ -1,6 +1,7 @@
 use grid::{Direction, Grid, Position};
 use std::fs;
 use std::time::Instant;
+use utils::par::Pool;
 
 #[derive(Debug, Clone, Copy)]
 struct EnergizedPoint {
@@ -172,7 +173,7 @@ fn solve1(filename: &str) -> u64 {
     energized.count_energized() as u64
 }
 
-fn solve2(filename: &str) -> u64 {
+fn solve2(filename: &str, pool: &Pool) -> u64 {
     let grid = make_grid(filename);
 
     let start_west =
@@ -197,17 +198,20 @@ fn solve2(filename: &str) -> u64 {
         )
     });
 
-    start_west
+    let starts: Vec<(Position, Direction)> = start_west
         .chain(start_east)
         .chain(start_north)
         .chain(start_south)
-        .map(|(start, heading)| {
-            let mut energized = EnergizedGrid::new_from_size(grid.width(), grid.height());
-            trace_light(&grid, start, heading, &mut energized);
-            energized.count_energized() as u64
-        })
-        .max()
-        .unwrap()
+        .collect();
+
+    pool.map(&starts, |(start, heading)| {
+        let mut energized = EnergizedGrid::new_from_size(grid.width(), grid.height());
+        trace_light(&grid, *start, *heading, &mut energized);
+        energized.count_energized() as u64
+    })
+    .into_iter()
+    .max()
+    .unwrap()
 }
 
 const PUZZLE_FILENAME: &str = "./src/puzzle.txt";
@@ -217,9 +221,15 @@ fn main() {
     println!("Result of 1: {}", solve1(PUZZLE_FILENAME));
     println!("Solved 1 in {:?}\n\n", start.elapsed());
 
-    let start = Instant::now();
-    println!("Result of 2: {}", solve2(PUZZLE_FILENAME));
-    println!("Solved 2 in {:?}", start.elapsed());
+    for pool in [Pool::new(1), Pool::from_env()] {
+        let start = Instant::now();
+        println!("Result of 2: {}", solve2(PUZZLE_FILENAME, &pool));
+        println!(
+            "Solved 2 on {} thread(s) in {:?}",
+            pool.threads(),
+            start.elapsed()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -235,6 +245,7 @@ mod tests {
 
     #[test]
     fn test2() {
-        assert_eq!(solve2(EXAMPLE_FILENAME), 51);
+        assert_eq!(solve2(EXAMPLE_FILENAME, &Pool::new(1)), 51);
+        assert_eq!(solve2(EXAMPLE_FILENAME, &Pool::new(4)), 51);
     }
 }
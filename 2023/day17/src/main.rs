@@ -0,0 +1,132 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::time::Instant;
+
+use grid::{Direction, Grid, Position};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct State {
+    pos: Position,
+    heading: Direction,
+    run: u32,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct OpenState {
+    state: State,
+    heat_loss: u32,
+}
+
+impl PartialOrd for OpenState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.heat_loss.cmp(&self.heat_loss)
+    }
+}
+
+/// Finds the lowest heat loss from the top left to the bottom right, the crucible has to move at
+/// least `min_run` blocks in a direction before it can turn or stop and at most `max_run` blocks
+fn least_heat_loss(grid: &Grid, min_run: u32, max_run: u32) -> Option<u32> {
+    let goal = Position::new(grid.width() as i32 - 1, grid.height() as i32 - 1);
+    let start = Position::new(0, 0);
+
+    let mut best: HashMap<State, u32> = HashMap::new();
+    let mut open = BinaryHeap::new();
+
+    for heading in [Direction::Right, Direction::Down] {
+        let state = State {
+            pos: start,
+            heading,
+            run: 0,
+        };
+        best.insert(state, 0);
+        open.push(OpenState {
+            state,
+            heat_loss: 0,
+        });
+    }
+
+    while let Some(OpenState { state, heat_loss }) = open.pop() {
+        if state.pos == goal && state.run >= min_run {
+            return Some(heat_loss);
+        }
+        if best.get(&state).is_some_and(|best| heat_loss > *best) {
+            continue;
+        }
+
+        let mut options = Vec::with_capacity(3);
+        if state.run < max_run {
+            options.push((state.heading, state.run + 1));
+        }
+        if state.run >= min_run {
+            options.push((state.heading.turn_left(), 1));
+            options.push((state.heading.turn_right(), 1));
+        }
+
+        for (heading, run) in options {
+            let pos = state.pos + heading;
+            let Some(loss) = grid.get(pos).and_then(|ch| ch.to_digit(10)) else {
+                continue;
+            };
+
+            let next = State { pos, heading, run };
+            let heat_loss = heat_loss + loss;
+            if best.get(&next).is_none_or(|best| heat_loss < *best) {
+                best.insert(next, heat_loss);
+                open.push(OpenState {
+                    state: next,
+                    heat_loss,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+fn solve1(input: &str) -> u64 {
+    let grid = Grid::from_text(input);
+
+    least_heat_loss(&grid, 0, 3).expect("Should reach the factory") as u64
+}
+
+fn solve2(input: &str) -> u64 {
+    let grid = Grid::from_text(input);
+
+    least_heat_loss(&grid, 4, 10).expect("Should reach the factory") as u64
+}
+
+const PUZZLE: &str = include_str!("./puzzle.txt");
+
+fn main() {
+    let start = Instant::now();
+    println!("Result of 1: {}", solve1(PUZZLE));
+    println!("Solved 1 in {:?}\n\n", start.elapsed());
+
+    let start = Instant::now();
+    println!("Result of 2: {}", solve2(PUZZLE));
+    println!("Solved 2 in {:?}", start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("./example.txt");
+    const EXAMPLE2: &str = include_str!("./example2.txt");
+
+    #[test]
+    fn test1() {
+        assert_eq!(solve1(EXAMPLE), 102);
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(solve2(EXAMPLE), 94);
+        assert_eq!(solve2(EXAMPLE2), 71);
+    }
+}
@@ -1,8 +1,10 @@
 use std::fs;
 use std::time::Instant;
 
+use grid::{polygon::Polygon, Direction};
+
 struct Instruction {
-    direction: char,
+    direction: Direction,
     steps: i64,
 }
 
@@ -11,19 +13,13 @@ impl Instruction {
         if use_rgb {
             let split = line.split_once('#').unwrap().1.strip_suffix(')').unwrap();
             Instruction {
-                direction: match split.chars().nth(5).unwrap() {
-                    '0' => 'R',
-                    '1' => 'D',
-                    '2' => 'L',
-                    '3' => 'U',
-                    _ => 'U',
-                },
+                direction: Direction::from_char(split.chars().nth(5).unwrap()),
                 steps: i64::from_str_radix(&split[0..5], 16).unwrap(),
             }
         } else {
             let mut split = line.split(' ');
             Instruction {
-                direction: split.next().unwrap().chars().nth(0).unwrap(),
+                direction: Direction::from_char(split.next().unwrap().chars().next().unwrap()),
                 steps: split.next().unwrap().parse().unwrap(),
             }
         }
@@ -34,55 +30,14 @@ fn solve(filename: &str, part2: bool) -> u64 {
     println!("Solving for file: {filename}");
     let input = fs::read_to_string(filename).expect("Should have been read");
 
-    let instructions: Vec<Instruction> = input
-        .lines()
-        .map(|line| Instruction::new_from_line(line, part2))
-        .collect();
-
-    let mut x_min = 0;
-    let mut x_max = 0;
-    let mut y_min = 0;
-    let mut y_max = 0;
-    let mut y = 0;
-    let mut x = 0;
-
-    for instr in instructions.iter() {
-        match instr.direction {
-            'R' => x += instr.steps,
-            'L' => x -= instr.steps,
-            'U' => y += instr.steps,
-            'D' => y -= instr.steps,
-            _ => (),
-        }
-
-        x_min = x_min.min(x);
-        x_max = x_max.max(x);
-        y_min = y_min.min(y);
-        y_max = y_max.max(y);
-    }
-
-    let mut x = -x_min;
-    let mut y = -y_min;
-    let mut sum = 0;
-
-    for instr in instructions.iter() {
-        let prev_x = x;
-        let prev_y = y;
-        match instr.direction {
-            'R' => x += instr.steps,
-            'L' => x -= instr.steps,
-            'U' => y += instr.steps,
-            'D' => y -= instr.steps,
-            _ => (),
-        }
-
-        sum += (prev_x * y) - (x * prev_y);
-    }
-
-    sum = sum.abs() / 2;
+    let lagoon = Polygon::from_steps(
+        input
+            .lines()
+            .map(|line| Instruction::new_from_line(line, part2))
+            .map(|instr| (instr.direction, instr.steps)),
+    );
 
-    sum += instructions.iter().map(|i| i.steps).sum::<i64>() / 2; // add the route walked
-    sum += 1; // Add 1 for some reason
+    let sum = lagoon.enclosed_points();
 
     println!("Sum: {sum}");
 
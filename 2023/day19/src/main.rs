@@ -1,22 +1,215 @@
+use std::collections::HashMap;
 use std::fs;
+use std::ops::Range;
 use std::time::Instant;
 
+type Part = [u64; 4];
+type PartRange = [Range<u64>; 4];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl Target {
+    fn new_from_str(input: &str) -> Self {
+        match input {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Condition {
+    category: usize,
+    less_than: bool,
+    value: u64,
+}
+
+impl Condition {
+    fn new_from_str(input: &str) -> Self {
+        let category = match &input[0..1] {
+            "x" => 0,
+            "m" => 1,
+            "a" => 2,
+            "s" => 3,
+            _ => panic!("Unknown category in {input}"),
+        };
+        Condition {
+            category,
+            less_than: &input[1..2] == "<",
+            value: input[2..].parse().unwrap(),
+        }
+    }
+
+    fn matches(&self, part: &Part) -> bool {
+        if self.less_than {
+            part[self.category] < self.value
+        } else {
+            part[self.category] > self.value
+        }
+    }
+
+    /// Splits the range into the part that matches the condition and the part that doesn't
+    fn split(&self, range: &PartRange) -> (Option<PartRange>, Option<PartRange>) {
+        let current = &range[self.category];
+        let (matching, remaining) = if self.less_than {
+            (
+                current.start..self.value.min(current.end),
+                self.value.max(current.start)..current.end,
+            )
+        } else {
+            (
+                (self.value + 1).max(current.start)..current.end,
+                current.start..(self.value + 1).min(current.end),
+            )
+        };
+
+        let with = |new: Range<u64>| {
+            (!new.is_empty()).then(|| {
+                let mut range = range.clone();
+                range[self.category] = new;
+                range
+            })
+        };
+        (with(matching), with(remaining))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+impl Rule {
+    fn new_from_str(input: &str) -> Self {
+        match input.split_once(':') {
+            Some((condition, target)) => Rule {
+                condition: Some(Condition::new_from_str(condition)),
+                target: Target::new_from_str(target),
+            },
+            None => Rule {
+                condition: None,
+                target: Target::new_from_str(input),
+            },
+        }
+    }
+}
+
+fn parse_workflow(line: &str) -> (String, Vec<Rule>) {
+    let (name, rules) = line
+        .strip_suffix('}')
+        .and_then(|line| line.split_once('{'))
+        .expect("Workflow should look like name{rules}");
+    (
+        name.to_string(),
+        rules.split(',').map(Rule::new_from_str).collect(),
+    )
+}
+
+fn parse_part(line: &str) -> Part {
+    let mut part = [0; 4];
+    line.trim_matches(['{', '}'])
+        .split(',')
+        .zip(part.iter_mut())
+        .for_each(|(rating, value)| *value = rating[2..].parse().unwrap());
+    part
+}
+
+fn parse_input(input: &str) -> (HashMap<String, Vec<Rule>>, Vec<Part>) {
+    let mut lines = input.lines();
+    let workflows = lines
+        .by_ref()
+        .take_while(|line| !line.is_empty())
+        .map(parse_workflow)
+        .collect();
+    let parts = lines.map(parse_part).collect();
+    (workflows, parts)
+}
+
+fn is_accepted(workflows: &HashMap<String, Vec<Rule>>, part: &Part) -> bool {
+    let mut current = "in";
+
+    loop {
+        let rule = workflows[current]
+            .iter()
+            .find(|rule| {
+                rule.condition
+                    .is_none_or(|condition| condition.matches(part))
+            })
+            .expect("The last rule should always match");
+
+        match &rule.target {
+            Target::Accept => return true,
+            Target::Reject => return false,
+            Target::Workflow(name) => current = name,
+        }
+    }
+}
+
+fn count_accepted(
+    workflows: &HashMap<String, Vec<Rule>>,
+    target: &Target,
+    range: PartRange,
+) -> u64 {
+    let name = match target {
+        Target::Accept => return range.iter().map(|r| r.end - r.start).product(),
+        Target::Reject => return 0,
+        Target::Workflow(name) => name,
+    };
+
+    let mut remaining = Some(range);
+    let mut sum = 0;
+
+    for rule in workflows[name].iter() {
+        let Some(range) = remaining.take() else {
+            break;
+        };
+
+        match rule.condition {
+            Some(condition) => {
+                let (matching, rest) = condition.split(&range);
+                if let Some(matching) = matching {
+                    sum += count_accepted(workflows, &rule.target, matching);
+                }
+                remaining = rest;
+            }
+            None => sum += count_accepted(workflows, &rule.target, range),
+        }
+    }
+
+    sum
+}
+
 fn solve1(filename: &str) -> u64 {
     println!("Solving for file: {filename}");
     let input = fs::read_to_string(filename).expect("Should have been read");
 
-    let _lines = input.lines();
+    let (workflows, parts) = parse_input(&input);
 
-    0
+    parts
+        .iter()
+        .filter(|part| is_accepted(&workflows, part))
+        .map(|part| part.iter().sum::<u64>())
+        .sum()
 }
 
 fn solve2(filename: &str) -> u64 {
     println!("Solving for file: {filename}");
     let input = fs::read_to_string(filename).expect("Should have been read");
 
-    let _lines = input.lines();
+    let (workflows, _) = parse_input(&input);
 
-    0
+    count_accepted(
+        &workflows,
+        &Target::Workflow("in".to_string()),
+        [1..4001, 1..4001, 1..4001, 1..4001],
+    )
 }
 
 const PUZZLE_FILENAME: &str = "./src/puzzle.txt";
@@ -37,13 +230,26 @@ mod tests {
 
     const EXAMPLE_FILENAME: &str = "./src/example.txt";
 
+    #[test]
+    fn test_split() {
+        let condition = Condition::new_from_str("m>2090");
+        let (matching, rest) = condition.split(&[1..4001, 1..3000, 1..4001, 1..4001]);
+        assert_eq!(matching.unwrap()[1], 2091..3000);
+        assert_eq!(rest.unwrap()[1], 1..2091);
+
+        let condition = Condition::new_from_str("a<2006");
+        let (matching, rest) = condition.split(&[1..4001, 1..4001, 2006..4001, 1..4001]);
+        assert!(matching.is_none());
+        assert_eq!(rest.unwrap()[2], 2006..4001);
+    }
+
     #[test]
     fn test1() {
-        assert_eq!(solve1(EXAMPLE_FILENAME), 0);
+        assert_eq!(solve1(EXAMPLE_FILENAME), 19114);
     }
 
     #[test]
     fn test2() {
-        assert_eq!(solve2(EXAMPLE_FILENAME), 0);
+        assert_eq!(solve2(EXAMPLE_FILENAME), 167409079868000);
     }
 }
@@ -1,180 +1,136 @@
-use std::collections::HashSet;
-use std::time::Instant;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
+use std::time::Instant;
 
-#[derive(Debug, Clone, Copy)]
-enum Heading {
-    North,
-    South,
-    East,
-    West,
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn manhatten_distance(&self, other: Point) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
+use grid::{Direction, Grid, Position};
+use utils::{params::Params, sequence::extrapolate};
 
-    fn step(&self, heading: Heading) -> Point {
-        match heading {
-            Heading::North => Point {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Heading::South => Point {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Heading::East => Point {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Heading::West => Point {
-                x: self.x - 1,
-                y: self.y,
-            },
-        }
-    }
+fn parse_grid(filename: &str) -> (Grid, Position) {
+    println!("Solving for file: {filename}");
+    let input = fs::read_to_string(filename).expect("Should have been read");
 
-    fn is_on_map(&self, map: &Vec<Vec<char>>) -> bool {
-        self.x >= 0 && self.y >= 0 && self.y < map.len() as i32 && self.x < map[0].len() as i32
-    }
+    let grid = Grid::from_text(&input);
+    let start = grid.find_one('S').expect("Should have found start");
+    (grid, start)
 }
 
-struct Grid {
-    grid: Vec<Vec<char>>,
-}
+/// Returns the distance to every garden plot within `max_steps` from the start, when `infinite`
+/// is set the map repeats in every direction
+fn distances(
+    grid: &Grid,
+    start: Position,
+    max_steps: u64,
+    infinite: bool,
+) -> HashMap<Position, u64> {
+    let width = grid.width() as i32;
+    let height = grid.height() as i32;
+    let is_plot = |pos: Position| {
+        let pos = if infinite {
+            Position::new(pos.x.rem_euclid(width), pos.y.rem_euclid(height))
+        } else {
+            pos
+        };
+        matches!(grid.get(pos), Some('.') | Some('S'))
+    };
 
-impl Grid {
-    fn new() -> Self {
-        Grid { grid: Vec::new() }
-    }
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut open = VecDeque::from([(start, 0)]);
 
-    fn from_string(filename: &str) -> Grid {
-        let input = fs::read_to_string(filename).expect("Should have been read");
-        let lines = input.lines();
-        let mut grid: Grid = Grid::new();
-        for line in lines {
-            grid.insert(line.chars().collect());
+    while let Some((pos, steps)) = open.pop_front() {
+        if steps == max_steps {
+            continue;
         }
-        return grid;
-    }
-
-    fn get_value(&self, point: Point) -> Option<char> {
-        self.grid
-            .get(point.y as usize)?
-            .get(point.x as usize)
-            .copied()
-    }
-
-    fn insert(&mut self, new: Vec<char>) {
-        self.grid.insert(0, new);
-    }
-}
 
-
-fn solve1(filename: &str, steps: i32) -> u64 {
-    println!("Solving for file: {filename}");
-
-    let mut grid = Grid::from_string(filename);
-
-
-    let mut start = None;
-    for y in 0..grid.grid.len() {
-        for x in 0..grid.grid[0].len() {
-            let point = Point{x: x as i32, y: y as i32};
-            if grid.get_value(point) == Some('S') {
-                start = Some(point);
+        for dir in Direction::all_directions() {
+            let neighbor = pos + dir;
+            if is_plot(neighbor) && !distances.contains_key(&neighbor) {
+                distances.insert(neighbor, steps + 1);
+                open.push_back((neighbor, steps + 1));
             }
         }
     }
 
-    let start = start.expect("Should have found start");
+    distances
+}
 
-    let mut set: HashSet<Point> = HashSet::new();
+/// Counts the plots that can be reached in exactly `steps`, which are all plots that are close
+/// enough and have the same parity because the elf can always step back and forth
+fn count_reachable(distances: &HashMap<Position, u64>, steps: u64) -> u64 {
+    distances
+        .values()
+        .filter(|distance| **distance <= steps && **distance % 2 == steps % 2)
+        .count() as u64
+}
 
-    set.insert(start);
+fn solve1(filename: &str, steps: u64) -> u64 {
+    let (grid, start) = parse_grid(filename);
 
-    for _ in 0..steps {
-        let mut new_set: HashSet<Point> = HashSet::new();
+    count_reachable(&distances(&grid, start, steps, false), steps)
+}
 
-        let mut insert_if_option = |point: Point| {
-            if point.is_on_map(&grid.grid) && grid.grid[point.y as usize][point.x as usize] == '.' {
-                new_set.insert(point);
-                grid.grid[point.y as usize][point.x as usize] = 'O';
-            }
-        };
+/// Returns true when the row and the column of the start have no rocks, which makes the walk
+/// reach every copy of the map at a predictable moment
+fn has_clear_start_lines(grid: &Grid, start: Position) -> bool {
+    let row = (0..grid.width() as i32).map(|x| Position::new(x, start.y));
+    let column = (0..grid.height() as i32).map(|y| Position::new(start.x, y));
 
-        for item in set.iter() {
-            insert_if_option(item.step(Heading::North));
-            insert_if_option(item.step(Heading::South));
-            insert_if_option(item.step(Heading::East));
-            insert_if_option(item.step(Heading::West));
-        }
+    row.chain(column).all(|pos| grid.get(pos) != Some('#'))
+}
 
-        set = new_set;
+fn count_infinite(grid: &Grid, start: Position, steps: u64, brute_force: bool) -> u64 {
+    // The extrapolation only holds for square maps with clear start lines, like the puzzle
+    if brute_force || grid.width() != grid.height() || !has_clear_start_lines(grid, start) {
+        return count_reachable(&distances(grid, start, steps, true), steps);
     }
 
+    // Every time the walk crosses another copy of the map the amount of plots grows
+    // quadratically. Sample three map widths and extrapolate.
+    let size = grid.width() as u64;
+    let remainder = steps % size;
+    let samples: Vec<u64> = (0..3).map(|i| remainder + i * size).collect();
 
-    let is_even = steps % 2 == 0;
-
-    let mut sum = 0;
-
-    for y in 0..grid.grid.len() {
-        for x in 0..grid.grid[0].len() {
-            let point = Point{x: x as i32, y: y as i32};
-            let distance = start.manhatten_distance(point);
-            let value = grid.get_value(point);
-            if distance <= steps && (value == Some('S') || value == Some('O')) {
-                if (is_even && distance % 2 == 0) || (!is_even && distance % 2 == 1) {
-                    sum += 1;
-                }
-            }
-        }
-    }
+    let distances = distances(grid, start, *samples.last().unwrap(), true);
+    let counts: Vec<i64> = samples
+        .iter()
+        .map(|steps| count_reachable(&distances, *steps) as i64)
+        .collect();
 
-    for line in grid.grid.iter().rev() {
-        println!("{}", line.iter().collect::<String>());
-    }
-
-    return sum;
+    extrapolate(&counts, (steps / size) as i64) as u64
 }
 
+fn solve2(filename: &str, steps: u64, brute_force: bool) -> u64 {
+    let (grid, start) = parse_grid(filename);
 
-fn solve2(filename: &str) -> u64 {
-    println!("Solving for file: {filename}");
-    let input = fs::read_to_string(filename).expect("Should have been read");
-
-    let lines = input.lines();
-
-    return 0;
+    count_infinite(&grid, start, steps, brute_force)
 }
 
-
-const PUZZLE_FILENAME: &'static str = "./src/puzzle.txt";
+const PUZZLE_FILENAME: &str = "./src/puzzle.txt";
 
 fn main() {
+    let params = Params::from_args();
+    let filename = params.input_path(PUZZLE_FILENAME);
+
     let start = Instant::now();
-    println!("Result of 1: {}", solve1(PUZZLE_FILENAME, 64));
+    println!("Result of 1: {}", solve1(filename, params.get("steps", 64)));
     println!("Solved 1 in {:?}\n\n", start.elapsed());
 
     let start = Instant::now();
-    println!("Result of 2: {}", solve2(PUZZLE_FILENAME));
+    println!(
+        "Result of 2: {}",
+        solve2(
+            filename,
+            params.get("steps2", 26501365),
+            params.get("brute_force", false)
+        )
+    );
     println!("Solved 2 in {:?}", start.elapsed());
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_FILENAME: &'static str = "./src/example.txt";
+    const EXAMPLE_FILENAME: &str = "./src/example.txt";
 
     #[test]
     fn test1() {
@@ -183,6 +139,47 @@ mod tests {
 
     #[test]
     fn test2() {
-        assert_eq!(solve2(EXAMPLE_FILENAME), 0);
+        assert_eq!(solve2(EXAMPLE_FILENAME, 6, true), 16);
+        assert_eq!(solve2(EXAMPLE_FILENAME, 10, true), 50);
+        assert_eq!(solve2(EXAMPLE_FILENAME, 50, true), 1594);
+        assert_eq!(solve2(EXAMPLE_FILENAME, 100, true), 6536);
+        assert_eq!(solve2(EXAMPLE_FILENAME, 500, true), 167004);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn extrapolation_matches_brute_force() {
+        let grid = Grid::from_text(
+            "\
+...........
+.##....##..
+.#.......#.
+...##..#.#.
+.#....#..#.
+.....S.....
+.##....#.#.
+..#....##..
+.##....##..
+.#.......#.
+...........",
+        );
+        let start = grid.find_one('S').unwrap();
+        assert!(has_clear_start_lines(&grid, start));
+
+        let size = grid.width() as u64;
+        for k in [3, 4, 7] {
+            let steps = k * size + size / 2;
+            assert_eq!(
+                count_infinite(&grid, start, steps, false),
+                count_infinite(&grid, start, steps, true),
+                "{steps} steps"
+            );
+        }
+    }
+
+    #[test]
+    fn blocked_start_lines_fall_back() {
+        let (grid, start) = parse_grid(EXAMPLE_FILENAME);
+        assert!(!has_clear_start_lines(&grid, start));
+        assert_eq!(solve2(EXAMPLE_FILENAME, 100, false), 6536);
+    }
+}
@@ -1,220 +1,116 @@
 use std::fs;
 use std::time::Instant;
 
-#[derive(Clone, Copy, PartialEq, Debug)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn x_offset(&self, offset: i32) -> Self {
-        Self {
-            x: self.x + offset,
-            y: self.y,
-        }
-    }
-    fn y_offset(&self, offset: i32) -> Self {
-        Self {
-            x: self.x,
-            y: self.y + offset,
-        }
-    }
-}
-
-#[derive(Debug)]
-struct Section {
-    start: Point,
-    end: Point,
-    len: u32,
-}
+use graph::{longest_path::longest_simple_path, Graph, NodeId};
+use grid::{Direction, Grid, Position};
 
-fn get_point(input: &[Vec<char>], point: Point) -> Option<char> {
-    if point.x >= 0
-        && point.x < input[0].len() as i32
-        && point.y >= 0
-        && point.y < input.len() as i32
-    {
-        Some(input[point.y as usize][point.x as usize])
-    } else {
-        None
-    }
+fn is_path(grid: &Grid, pos: Position) -> bool {
+    matches!(grid.get(pos), Some(ch) if ch != '#')
 }
 
-fn is_crossing(input: &[Vec<char>], point: Point) -> bool {
-    [(-1, 0), (1, 0), (0, -1), (0, 1)]
-        .iter()
-        .filter(|(x, y)| {
-            matches!(
-                get_point(input, point.x_offset(*x).y_offset(*y)),
-                Some('>') | Some('v')
-            )
-        })
-        .count()
-        >= 3
+fn is_junction(grid: &Grid, pos: Position) -> bool {
+    is_path(grid, pos)
+        && Direction::all_directions()
+            .iter()
+            .filter(|dir| is_path(grid, pos + **dir))
+            .count()
+            >= 3
 }
 
-fn get_next_points(input: &[Vec<char>], current: Point) -> Vec<Point> {
-    [(-1, 0), (1, 0), (0, -1), (0, 1)]
-        .iter()
-        .filter_map(|(x, y)| {
-            let point = current.x_offset(*x).y_offset(*y);
-            if let Some(ch) = get_point(input, point) {
-                if ch.is_ascii_uppercase() {
-                    return Some(point);
-                }
-                if ".>v".contains(ch) {
-                    return Some(point);
-                }
-            }
-            None
-        })
-        .collect()
-}
-
-fn get_downhill_points(input: &[Vec<char>], current: Point) -> Vec<Point> {
-    [(1, 0, '>'), (0, 1, 'v')]
-        .iter()
-        .filter_map(|(x, y, downhill)| {
-            let point = current.x_offset(*x).y_offset(*y);
-            if get_point(input, point) == Some(*downhill) {
-                Some(point)
-            } else {
-                None
-            }
-        })
-        .collect()
+fn node_name(pos: Position) -> String {
+    format!("{},{}", pos.x, pos.y)
 }
 
-fn travel_sections(input: &mut [Vec<char>], start: Point, end: Point, output: &mut Vec<Section>) {
-    let mut steps = 0;
-    let mut current = start;
+/// Follows the corridor from the junction in the given direction until the next junction, returns
+/// that junction with the amount of steps, or `None` for dead ends and slopes that block the way
+fn follow_corridor(
+    grid: &Grid,
+    junctions: &[Position],
+    from: Position,
+    direction: Direction,
+    slippery: bool,
+) -> Option<(Position, u64)> {
+    let mut previous = from;
+    let mut current = from + direction;
+    let mut heading = direction;
+    let mut steps = 1;
 
     loop {
-        let mut next_points = get_next_points(input, current);
-
-        if next_points.is_empty() {
-            if current == end {
-                output.push(Section {
-                    start,
-                    end,
-                    len: steps,
-                });
-            } else if get_point(input, current) == Some('X') {
-                output.push(Section {
-                    start,
-                    end: current,
-                    len: steps + 1,
-                });
-            } else {
-                println!("Found the end at {:?}", current);
+        if !is_path(grid, current) {
+            return None;
+        }
+        let blocked = match grid.get(current) {
+            Some(slope @ ('^' | 'v' | '<' | '>')) => {
+                slippery && Direction::from_char(slope) != heading
             }
-            break;
+            _ => false,
+        };
+        if blocked {
+            return None;
         }
-
-        if next_points.len() == 2 {
-            next_points = next_points
-                .iter()
-                .filter(|point| !get_point(input, **point).unwrap().is_ascii_uppercase())
-                .copied()
-                .collect();
+        if junctions.contains(&current) {
+            return Some((current, steps));
         }
 
-        if next_points.len() == 1 {
-            input[current.y as usize][current.x as usize] = ' ';
-            steps += 1;
-
-            current = next_points[0];
-        } else {
-            output.push(Section {
-                start,
-                end: current,
-                len: steps + 1,
-            });
-
-            for next in get_downhill_points(input, current) {
-                println!("Next: {:?}", next);
-                travel_sections(input, next, end, output);
-            }
-            break;
-        }
+        let (next, next_heading) = Direction::all_directions()
+            .iter()
+            .map(|dir| (current + *dir, *dir))
+            .find(|(next, _)| *next != previous && is_path(grid, *next))?;
+
+        previous = current;
+        current = next;
+        heading = next_heading;
+        steps += 1;
     }
 }
 
-fn calculate_path(start: Point, sections: &[Section]) -> u64 {
-    let mut max = 0;
-
-    for section in sections.iter().filter(|x| x.start == start) {
-        println!("Try section: {:?}", section);
-        let pathlen = calculate_path(section.end.x_offset(1), sections) + section.len as u64;
-        if pathlen > max {
-            max = pathlen;
-        }
-        let pathlen = calculate_path(section.end.y_offset(1), sections) + section.len as u64;
-        if pathlen > max {
-            max = pathlen;
-        }
+/// Compresses the maze into a graph of the junctions connected by the corridor lengths
+fn compress(grid: &Grid, slippery: bool) -> (Graph, NodeId, NodeId) {
+    let start = Position::new(1, 0);
+    let end = Position::new(grid.width() as i32 - 2, grid.height() as i32 - 1);
+
+    let mut junctions = vec![start, end];
+    junctions.extend(
+        grid.iter()
+            .map(|(pos, _)| pos)
+            .filter(|pos| is_junction(grid, *pos)),
+    );
+
+    let mut graph = Graph::new();
+    for junction in junctions.iter() {
+        graph.node(&node_name(*junction));
     }
 
-    max
-}
-
-fn parse_input(mut input: Vec<Vec<char>>) -> u64 {
-    let start = Point { x: 1, y: 0 };
-    let end = Point {
-        x: input[0].len() as i32 - 2,
-        y: input.len() as i32 - 1,
-    };
-
-    println!("End: {:?}", end);
-
-    input[0][1] = 'A';
-
-    let y = input.len();
-    let x = input[0].len();
-    input[y - 1][x - 2] = 'Z';
-
-    for y in 0..input.len() {
-        for x in 0..input[0].len() {
-            if is_crossing(
-                &input,
-                Point {
-                    x: x as i32,
-                    y: y as i32,
-                },
-            ) {
-                input[y][x] = 'X';
+    for junction in junctions.iter() {
+        for dir in Direction::all_directions() {
+            if let Some((next, steps)) = follow_corridor(grid, &junctions, *junction, dir, slippery)
+            {
+                graph.add_edge(&node_name(*junction), &node_name(next), steps);
             }
         }
     }
 
-    let mut output = Vec::new();
-    travel_sections(&mut input, start, end, &mut output);
-
-    for line in input {
-        println!("Line: {}", line.iter().collect::<String>());
-    }
-    println!("Output {:#?}", output);
-
-    calculate_path(start, &output)
+    let start = graph.id(&node_name(start)).unwrap();
+    let end = graph.id(&node_name(end)).unwrap();
+    (graph, start, end)
 }
 
-fn solve1(filename: &str) -> u64 {
+fn solve(filename: &str, slippery: bool) -> u64 {
     println!("Solving for file: {filename}");
     let input = fs::read_to_string(filename).expect("Should have been read");
 
-    let lines: Vec<Vec<char>> = input.lines().map(|v| v.chars().collect()).collect();
+    let grid = Grid::from_text(&input);
+    let (graph, start, end) = compress(&grid, slippery);
 
-    parse_input(lines)
+    longest_simple_path(&graph, start, end).expect("Should be able to reach the end")
 }
 
-fn solve2(filename: &str) -> u64 {
-    println!("Solving for file: {filename}");
-    let input = fs::read_to_string(filename).expect("Should have been read");
-
-    let _lines = input.lines();
+fn solve1(filename: &str) -> u64 {
+    solve(filename, true)
+}
 
-    0
+fn solve2(filename: &str) -> u64 {
+    solve(filename, false)
 }
 
 const PUZZLE_FILENAME: &str = "./src/puzzle.txt";
@@ -1,130 +1,70 @@
 use std::time::Instant;
 
-use utils::gaussian_elimination;
-
-#[derive(Debug, Clone, Copy, Default)]
-struct Coord {
-    x: i128,
-    y: i128,
-    z: i128,
-}
-
-impl Coord {
-    fn new_from_vec(input: &[i128]) -> Self {
-        Coord {
-            x: input[0],
-            y: input[1],
-            z: input[2],
-        }
-    }
-}
+use grid::space::{Distance3, Line, Position3};
+use utils::{gaussian_elimination, params::Params, rational::Rational};
 
 #[derive(Debug)]
 struct Hailstone {
-    position: Coord,
-    velocity: Coord,
+    position: Position3,
+    velocity: Distance3,
+}
+
+fn parse_triple(input: &str) -> (i64, i64, i64) {
+    let values: Vec<i64> = input
+        .split(',')
+        .map(|pos| pos.trim().parse::<i64>().unwrap())
+        .collect();
+    (values[0], values[1], values[2])
 }
 
 impl Hailstone {
     fn new_from_line(line: &str) -> Self {
         let (position, velocity) = line.split_once('@').unwrap();
-        let positions: Vec<i128> = position
-            .split(',')
-            .map(|pos| pos.trim().parse::<i128>().unwrap())
-            .collect();
-        let velocities: Vec<i128> = velocity
-            .split(',')
-            .map(|pos| pos.trim().parse::<i128>().unwrap())
-            .collect();
+        let (x, y, z) = parse_triple(position);
+        let (dx, dy, dz) = parse_triple(velocity);
         Hailstone {
-            position: Coord::new_from_vec(positions.as_slice()),
-            velocity: Coord::new_from_vec(velocities.as_slice()),
+            position: Position3::new(x, y, z),
+            velocity: Distance3::new(dx, dy, dz),
         }
     }
 
-    fn future_stone(&self, time: i128) -> Self {
-        let mut new_pos = self.position;
-        new_pos.x += self.velocity.x * time;
-        new_pos.y += self.velocity.y * time;
-        new_pos.z += self.velocity.z * time;
-
-        Hailstone {
-            position: new_pos,
-            velocity: self.velocity,
-        }
-    }
-
-    fn intersect_xy(&self, other: &Hailstone) -> Option<Coord> {
-        let x1 = self.position.x;
-        let y1 = self.position.y;
-        let x3 = other.position.x;
-        let y3 = other.position.y;
-
-        let future_self = self.future_stone(1_000_000_000_000_000);
-        let future_other = other.future_stone(1_000_000_000_000_000);
-        let x2 = future_self.position.x;
-        let y2 = future_self.position.y;
-        let x4 = future_other.position.x;
-        let y4 = future_other.position.y;
-
-        let den = ((x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4)) as f64;
-
-        let t = ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) as f64 / den;
-        let u = ((x1 - x3) * (y1 - y2) - (y1 - y3) * (x1 - x2)) as f64 / den;
-
-        // println!("t: {t}");
-        // println!("u: {u}");
-
-        if !(0f64..=1f64).contains(&t) || !(0f64..=1f64).contains(&u) {
-            return None;
-        }
-
-        let x = x1 as f64 + t * (x2 - x1) as f64;
-        let y = y1 as f64 + t * (y2 - y1) as f64;
-
-        // println!("P: {}, {}", x, y);
-        Some(Coord {
-            x: x as i128,
-            y: y as i128,
-            z: 0,
-        })
+    fn path(&self) -> Line {
+        Line::ray(self.position, self.velocity)
     }
 
     fn to_linear_equation_xy(&self, other: &Self) -> Vec<f64> {
-        [
-            self.velocity.y - other.velocity.y,
-            other.position.y - self.position.y,
-            other.velocity.x - self.velocity.x,
-            self.position.x - other.position.x,
-            other.position.y * other.velocity.x - other.position.x * other.velocity.y
-                + self.position.x * self.velocity.y
-                - self.position.y * self.velocity.x,
-        ]
-        .iter()
-        .map(|x| *x as f64)
-        .collect()
+        let (p1, v1) = (self.position, self.velocity);
+        let (p2, v2) = (other.position, other.velocity);
+        linear_equation((p1.x, p1.y), (v1.x, v1.y), (p2.x, p2.y), (v2.x, v2.y))
     }
 
     fn to_linear_equation_xz(&self, other: &Self) -> Vec<f64> {
-        [
-            self.velocity.z - other.velocity.z,
-            other.position.z - self.position.z,
-            other.velocity.x - self.velocity.x,
-            self.position.x - other.position.x,
-            other.position.z * other.velocity.x - other.position.x * other.velocity.z
-                + self.position.x * self.velocity.z
-                - self.position.z * self.velocity.x,
-        ]
-        .iter()
-        .map(|x| *x as f64)
-        .collect()
+        let (p1, v1) = (self.position, self.velocity);
+        let (p2, v2) = (other.position, other.velocity);
+        linear_equation((p1.x, p1.z), (v1.x, v1.z), (p2.x, p2.z), (v2.x, v2.z))
     }
 }
 
-fn solve1(input: &str, min: i128, max: i128) -> u64 {
-    let hailstones: Vec<Hailstone> = input.lines().map(Hailstone::new_from_line).collect();
+/// Returns the coefficients of the equation for the rock in x and one other axis, calculated in
+/// `i128` because the products of puzzle positions and velocities come close to the `i64` limit
+fn linear_equation(p1: (i64, i64), v1: (i64, i64), p2: (i64, i64), v2: (i64, i64)) -> Vec<f64> {
+    let [p1x, p1o, v1x, v1o, p2x, p2o, v2x, v2o] =
+        [p1.0, p1.1, v1.0, v1.1, p2.0, p2.1, v2.0, v2.1].map(i128::from);
+    [
+        v1o - v2o,
+        p2o - p1o,
+        v2x - v1x,
+        p1x - p2x,
+        p2o * v2x - p2x * v2o + p1x * v1o - p1o * v1x,
+    ]
+    .iter()
+    .map(|x| *x as f64)
+    .collect()
+}
 
-    // println!("Hailstones: {:?}", hailstones);
+fn solve1(input: &str, min: i64, max: i64) -> u64 {
+    let hailstones: Vec<Hailstone> = input.lines().map(Hailstone::new_from_line).collect();
+    let (min, max) = (Rational::from(min), Rational::from(max));
 
     let mut it = hailstones.iter();
 
@@ -133,8 +73,9 @@ fn solve1(input: &str, min: i128, max: i128) -> u64 {
     for stone in hailstones.iter() {
         it.next();
         for stone2 in it.clone() {
-            if let Some(coord) = stone.intersect_xy(stone2) {
-                if coord.x >= min && coord.x <= max && coord.y >= min && coord.y <= max {
+            if let Some(intersection) = stone.path().intersect_xy(&stone2.path()) {
+                let [x, y, _] = intersection.point;
+                if [x, y].iter().all(|v| (min..=max).contains(v)) {
                     sum += 1;
                 }
             }
@@ -177,15 +118,22 @@ fn solve2(input: &str) -> i128 {
 const PUZZLE: &str = include_str!("./puzzle.txt");
 
 fn main() {
+    let params = Params::from_args();
+    let input = params.input(PUZZLE);
+
     let start = Instant::now();
     println!(
         "Result of 1: {}",
-        solve1(PUZZLE, 200000000000000, 400000000000000)
+        solve1(
+            &input,
+            params.get("min", 200000000000000),
+            params.get("max", 400000000000000)
+        )
     );
     println!("Solved 1 in {:?}\n\n", start.elapsed());
 
     let start = Instant::now();
-    println!("Result of 2: {}", solve2(PUZZLE));
+    println!("Result of 2: {}", solve2(&input));
     println!("Solved 2 in {:?}", start.elapsed());
 }
 
@@ -1,64 +1,63 @@
 use std::time::Instant;
 
-fn parse_number(input: &str) -> Option<u64> {
-    if input.is_empty() || input.len() > 3 || !input.chars().all(|ch| ch.is_numeric()) {
-        None
-    } else {
-        input.parse().ok()
-    }
-}
+use utils::pattern::{Pattern, Token, Tokenizer};
 
-fn solve1(mut input: &str) -> u64 {
-    let mut multipliers = Vec::new();
-
-    while let Some(idx) = input.find("mul(") {
-        input = &input[idx..];
-        input = input.strip_prefix("mul(").expect("Prefix already found");
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Instruction {
+    Mul,
+    Do,
+    Dont,
+}
 
-        if let Some((val, remainder)) = input.split_once(')') {
-            if let Some((a, b)) = val.split_once(',') {
-                if let (Some(a), Some(b)) = (parse_number(a), parse_number(b)) {
-                    multipliers.push((a, b));
-                    input = remainder; // Only use when actually matches
-                }
-            }
-        }
-    }
+fn tokenizer() -> Tokenizer<Instruction> {
+    let number = Pattern::capture(Pattern::digits(1, 3));
+
+    Tokenizer::new()
+        .token(
+            Instruction::Mul,
+            Pattern::sequence([
+                Pattern::literal("mul("),
+                number.clone(),
+                Pattern::literal(","),
+                number,
+                Pattern::literal(")"),
+            ]),
+        )
+        .token(Instruction::Do, Pattern::literal("do()"))
+        .token(Instruction::Dont, Pattern::literal("don't()"))
+}
 
-    multipliers.iter().map(|(a, b)| a * b).sum()
+fn multiply(token: &Token<Instruction>) -> u64 {
+    token
+        .matched
+        .captures
+        .iter()
+        .map(|nr| nr.parse::<u64>().unwrap())
+        .product()
 }
 
-fn solve2(mut input: &str) -> u64 {
-    let mut multipliers = Vec::new();
+fn solve1(input: &str) -> u64 {
+    tokenizer()
+        .tokens(input)
+        .filter(|token| token.kind == Instruction::Mul)
+        .map(|token| multiply(&token))
+        .sum()
+}
 
+fn solve2(input: &str) -> u64 {
     let mut enabled = true;
-
-    while !input.is_empty() {
-        if let Some(val) = input.strip_prefix("don't()") {
-            input = val;
-            enabled = false;
-        } else if let Some(val) = input.strip_prefix("do()") {
-            input = val;
-            enabled = true;
-        } else if let Some(val) = input.strip_prefix("mul(") {
-            input = val;
-
-            if let Some((val, remainder)) = input.split_once(')') {
-                if let Some((a, b)) = val.split_once(',') {
-                    if let (Some(a), Some(b)) = (parse_number(a), parse_number(b)) {
-                        if enabled {
-                            multipliers.push((a, b));
-                        }
-                        input = remainder; // Only use when actually matches
-                    }
-                }
-            }
-        } else {
-            input = &input[1..];
+    let mut total = 0;
+
+    for token in tokenizer().tokens(input) {
+        match token.kind {
+            Instruction::Do => enabled = true,
+            Instruction::Dont => enabled = false,
+            Instruction::Mul if enabled => total += multiply(&token),
+            Instruction::Mul => (),
         }
     }
 
-    multipliers.iter().map(|(a, b)| a * b).sum()
+    total
 }
 
 const PUZZLE: &str = include_str!("./puzzle.txt");
@@ -77,16 +76,8 @@ fn main() {
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = include_str!("./example.txt");
-    const EXAMPLE2: &str = include_str!("./example2.txt");
-
-    #[test]
-    fn test1() {
-        assert_eq!(solve1(EXAMPLE), 161);
-    }
-
-    #[test]
-    fn test2() {
-        assert_eq!(solve2(EXAMPLE2), 48);
+    example_tests::example_tests! {
+        part1 = |example| solve1(example.input);
+        part2 = |example| solve2(example.input);
     }
 }
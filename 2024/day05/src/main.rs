@@ -1,7 +1,6 @@
-use std::{
-    collections::{hash_map::Entry, HashMap},
-    time::Instant,
-};
+use std::time::Instant;
+
+use graph::partial_order::PartialOrder;
 
 fn parse_rule(input: &str) -> Option<(u32, u32)> {
     input
@@ -13,79 +12,38 @@ fn parse_print_run(input: &str) -> Vec<u32> {
     input.split(',').map(|a| a.parse().unwrap()).collect()
 }
 
-fn solve1(input: &str) -> u64 {
+fn parse(input: &str) -> (PartialOrder<u32>, Vec<Vec<u32>>) {
     let mut lines = input.lines();
 
-    let rules: Vec<(u32, u32)> = lines.by_ref().map_while(parse_rule).collect();
+    let rules = lines.by_ref().map_while(parse_rule).collect();
+    let updates = lines.map(parse_print_run).collect();
 
-    let mut rulemap: HashMap<u32, Vec<u32>> = HashMap::new();
-
-    rules
-        .iter()
-        .for_each(|(a, b)| rulemap.entry(*a).or_default().push(*b));
-
-    let updates: Vec<Vec<u32>> = lines.map(parse_print_run).collect();
-
-    let mut sum = 0;
-
-    for update in updates {
-        if update
-            .iter()
-            .enumerate()
-            .all(|(i, val)| match rulemap.entry(*val) {
-                Entry::Occupied(rules) => update[0..i].iter().all(|x| !rules.get().contains(x)),
-                _ => true,
-            })
-        {
-            sum += update[update.len() / 2] as u64;
-        }
-    }
-
-    sum
+    (rules, updates)
 }
 
-fn sort_update(mut update: Vec<u32>, rulemap: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
-    update.sort_by(|a, b| {
-        if let Some(rules) = rulemap.get(a) {
-            if rules.contains(b) {
-                return std::cmp::Ordering::Greater;
-            }
-        }
-        std::cmp::Ordering::Less
-    });
-    update
+fn solve1(input: &str) -> u64 {
+    let (rules, updates) = parse(input);
+
+    updates
+        .iter()
+        .filter(|update| rules.is_ordered(update))
+        .map(|update| update[update.len() / 2] as u64)
+        .sum()
 }
 
 fn solve2(input: &str) -> u64 {
-    let mut lines = input.lines();
-
-    let rules: Vec<(u32, u32)> = lines.by_ref().map_while(parse_rule).collect();
+    let (rules, updates) = parse(input);
 
-    let mut rulemap: HashMap<u32, Vec<u32>> = HashMap::new();
-
-    rules
+    updates
         .iter()
-        .for_each(|(a, b)| rulemap.entry(*a).or_default().push(*b));
-
-    let updates: Vec<Vec<u32>> = lines.map(parse_print_run).collect();
-
-    let mut sum = 0;
-
-    for update in updates {
-        if !update
-            .iter()
-            .enumerate()
-            .all(|(i, val)| match rulemap.entry(*val) {
-                Entry::Occupied(rules) => update[0..i].iter().all(|x| !rules.get().contains(x)),
-                _ => true,
-            })
-        {
-            let update = sort_update(update, &rulemap);
-            sum += update[update.len() / 2] as u64;
-        }
-    }
-
-    sum
+        .filter(|update| !rules.is_ordered(update))
+        .map(|update| {
+            let update = rules
+                .order(update)
+                .unwrap_or_else(|contradiction| panic!("{contradiction}"));
+            update[update.len() / 2] as u64
+        })
+        .sum()
 }
 
 const PUZZLE: &str = include_str!("./puzzle.txt");
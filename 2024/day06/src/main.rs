@@ -1,5 +1,6 @@
-use grid::{Direction, Grid, Position};
+use grid::{image::Rgb, recorder::Recorder, Direction, Grid, Position};
 use std::{collections::HashSet, time::Instant};
+use utils::par::Pool;
 
 fn get_char(dir: Direction) -> char {
     match dir {
@@ -42,20 +43,37 @@ fn walk_path(map: &mut Grid, mut pos: Position, mut dir: Direction) -> Outcome {
     }
 }
 
-fn solve1(input: &str) -> u64 {
+fn palette(ch: char) -> Rgb {
+    match ch {
+        '#' => Rgb::BLACK,
+        '.' => Rgb::WHITE,
+        _ => Rgb::RED,
+    }
+}
+
+fn solve1(input: &str, recorder: &mut Recorder) -> u64 {
     let mut map = Grid::from_text(input);
 
-    let pos = map.find_one('^').expect("To have start position");
+    let mut pos = map.find_one('^').expect("To have start position");
 
     // Start with up direction
-    if walk_path(&mut map, pos, Direction::Up) == Outcome::Loop {
+    let mut dir = Direction::Up;
+    recorder.capture(&map);
+    let outcome = loop {
+        if let Some(outcome) = set_step(&mut map, &mut pos, &mut dir) {
+            break outcome;
+        }
+        recorder.capture(&map);
+    };
+
+    if outcome == Outcome::Loop {
         return 0;
     }
 
     map.count_filtered(|ch| ch != '#' && ch != '.') as u64
 }
 
-fn solve2(input: &str) -> u64 {
+fn solve2(input: &str, pool: &Pool) -> u64 {
     let mut clean_map = Grid::from_text(input);
 
     let start = clean_map.find_one('^').expect("To have start position");
@@ -68,40 +86,57 @@ fn solve2(input: &str) -> u64 {
 
     let mut tracker = clean_map.clone();
 
-    let mut set: HashSet<Position> = HashSet::new();
+    let mut seen: HashSet<Position> = HashSet::new();
+    let mut blocks = Vec::new();
 
     let mut dir = Direction::Up;
     let mut pos = start;
 
+    // Every cell in front of the guard on the original path is a candidate for a new obstacle
     loop {
         if set_step(&mut tracker, &mut pos, &mut dir) == Some(Outcome::OutOfMap) {
             break;
         }
-        let mut map = clean_map.clone();
-
         let block = pos.step(dir);
-        if let Some(ch) = map.get_mut(block) {
-            *ch = '#';
+        if seen.insert(block) {
+            blocks.push(block);
         }
+    }
+
+    pool.map(&blocks, |block| {
+        let mut map = clean_map.clone();
 
-        if walk_path(&mut map, start, Direction::Up) == Outcome::Loop {
-            set.insert(block);
+        if let Some(ch) = map.get_mut(*block) {
+            *ch = '#';
         }
-    }
 
-    set.len() as u64
+        walk_path(&mut map, start, Direction::Up) == Outcome::Loop
+    })
+    .into_iter()
+    .filter(|is_loop| *is_loop)
+    .count() as u64
 }
 
 const PUZZLE: &str = include_str!("./puzzle.txt");
 
 fn main() {
     let start = Instant::now();
-    println!("Result of 1: {}", solve1(PUZZLE));
+    let mut recorder = Recorder::from_env().skip_unchanged(true);
+    println!("Result of 1: {}", solve1(PUZZLE, &mut recorder));
     println!("Solved 1 in {:?}\n\n", start.elapsed());
-
-    let start = Instant::now();
-    println!("Result of 2: {}", solve2(PUZZLE));
-    println!("Solved 2 in {:?}", start.elapsed());
+    recorder
+        .save("day06_guard", 4, palette)
+        .expect("Recording to be written");
+
+    for pool in [Pool::new(1), Pool::from_env()] {
+        let start = Instant::now();
+        println!("Result of 2: {}", solve2(PUZZLE, &pool));
+        println!(
+            "Solved 2 on {} thread(s) in {:?}",
+            pool.threads(),
+            start.elapsed()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -112,11 +147,12 @@ mod tests {
 
     #[test]
     fn test1() {
-        assert_eq!(solve1(EXAMPLE), 41);
+        assert_eq!(solve1(EXAMPLE, &mut Recorder::disabled()), 41);
     }
 
     #[test]
     fn test2() {
-        assert_eq!(solve2(EXAMPLE), 6);
+        assert_eq!(solve2(EXAMPLE, &Pool::new(1)), 6);
+        assert_eq!(solve2(EXAMPLE, &Pool::new(4)), 6);
     }
 }
@@ -1,4 +1,5 @@
 use std::time::Instant;
+use utils::par::Pool;
 
 fn parse(line: &str) -> (u64, Vec<u64>) {
     let (sum, values) = line.split_once(':').expect("Should contain ':'");
@@ -32,14 +33,17 @@ fn is_valid(sum: u64, values: &[u64]) -> bool {
     is_valid_internal(values[0], &values[1..], sum)
 }
 
-fn solve1(input: &str) -> u64 {
-    let lines = input.lines();
-
-    lines
-        .map(parse)
-        .filter(|(sum, values)| is_valid(*sum, values))
-        .map(|(sum, _)| sum)
-        .sum()
+fn solve1(input: &str, pool: &Pool) -> u64 {
+    let lines: Vec<&str> = input.lines().collect();
+
+    pool.sum(&lines, |line| {
+        let (sum, values) = parse(line);
+        if is_valid(sum, &values) {
+            sum
+        } else {
+            0
+        }
+    })
 }
 
 fn concat(a: u64, b: u64) -> u64 {
@@ -68,26 +72,33 @@ fn is_valid2(sum: u64, values: &[u64]) -> bool {
     is_valid_internal2(values[0], &values[1..], sum)
 }
 
-fn solve2(input: &str) -> u64 {
-    let lines = input.lines();
-
-    lines
-        .map(parse)
-        .filter(|(sum, values)| is_valid2(*sum, values))
-        .map(|(sum, _)| sum)
-        .sum()
+fn solve2(input: &str, pool: &Pool) -> u64 {
+    let lines: Vec<&str> = input.lines().collect();
+
+    pool.sum(&lines, |line| {
+        let (sum, values) = parse(line);
+        if is_valid2(sum, &values) {
+            sum
+        } else {
+            0
+        }
+    })
 }
 
 const PUZZLE: &str = include_str!("./puzzle.txt");
 
 fn main() {
-    let start = Instant::now();
-    println!("Result of 1: {}", solve1(PUZZLE));
-    println!("Solved 1 in {:?}\n\n", start.elapsed());
+    for pool in [Pool::new(1), Pool::from_env()] {
+        println!("Using {} thread(s)", pool.threads());
 
-    let start = Instant::now();
-    println!("Result of 2: {}", solve2(PUZZLE));
-    println!("Solved 2 in {:?}", start.elapsed());
+        let start = Instant::now();
+        println!("Result of 1: {}", solve1(PUZZLE, &pool));
+        println!("Solved 1 in {:?}\n\n", start.elapsed());
+
+        let start = Instant::now();
+        println!("Result of 2: {}", solve2(PUZZLE, &pool));
+        println!("Solved 2 in {:?}\n\n", start.elapsed());
+    }
 }
 
 #[cfg(test)]
@@ -105,11 +116,13 @@ mod tests {
 
     #[test]
     fn test1() {
-        assert_eq!(solve1(EXAMPLE), 3749);
+        assert_eq!(solve1(EXAMPLE, &Pool::new(1)), 3749);
+        assert_eq!(solve1(EXAMPLE, &Pool::new(4)), 3749);
     }
 
     #[test]
     fn test2() {
-        assert_eq!(solve2(EXAMPLE), 11387);
+        assert_eq!(solve2(EXAMPLE, &Pool::new(1)), 11387);
+        assert_eq!(solve2(EXAMPLE, &Pool::new(4)), 11387);
     }
 }
@@ -1,11 +1,10 @@
-use std::{
-    collections::{hash_map::Entry, HashMap},
-    time::Instant,
-};
+use std::time::Instant;
+
+use utils::memo::Memo;
 
 fn split_even_length(val: u64) -> Option<(u64, u64)> {
     let log = val.ilog10() + 1;
-    if log % 2 == 0 {
+    if log.is_multiple_of(2) {
         let div = 10u64.pow(log / 2);
         Some((val / div, val % div))
     } else {
@@ -13,27 +12,21 @@ fn split_even_length(val: u64) -> Option<(u64, u64)> {
     }
 }
 
-fn blink(stone: u64, blinks_remaining: u64, memory: &mut HashMap<(u64, u64), u64>) -> u64 {
+fn blink(stone: u64, blinks_remaining: u64, memory: &mut Memo<(u64, u64), u64>) -> u64 {
     if blinks_remaining == 0 {
         return 1;
     }
     let blinks_remaining = blinks_remaining - 1;
 
-    if let Entry::Occupied(stones) = memory.entry((stone, blinks_remaining)) {
-        return *stones.get();
-    }
-
-    let result = if stone == 0 {
-        blink(1, blinks_remaining, memory)
-    } else if let Some((a, b)) = split_even_length(stone) {
-        blink(a, blinks_remaining, memory) + blink(b, blinks_remaining, memory)
-    } else {
-        blink(stone * 2024, blinks_remaining, memory)
-    };
-
-    memory.insert((stone, blinks_remaining), result);
-
-    result
+    memory.get_or_compute((stone, blinks_remaining), |memory| {
+        if stone == 0 {
+            blink(1, blinks_remaining, memory)
+        } else if let Some((a, b)) = split_even_length(stone) {
+            blink(a, blinks_remaining, memory) + blink(b, blinks_remaining, memory)
+        } else {
+            blink(stone * 2024, blinks_remaining, memory)
+        }
+    })
 }
 
 fn solve1(input: &str) -> u64 {
@@ -42,7 +35,7 @@ fn solve1(input: &str) -> u64 {
         .map(|x| x.parse().unwrap())
         .collect();
 
-    let mut map = HashMap::new();
+    let mut map = Memo::new();
 
     stones.iter().map(|stone| blink(*stone, 25, &mut map)).sum()
 }
@@ -53,7 +46,7 @@ fn solve2(input: &str) -> u64 {
         .map(|x| x.parse().unwrap())
         .collect();
 
-    let mut map = HashMap::new();
+    let mut map = Memo::new();
 
     stones.iter().map(|stone| blink(*stone, 75, &mut map)).sum()
 }
@@ -70,9 +63,37 @@ fn main() {
     println!("Solved 2 in {:?}", start.elapsed());
 }
 
+/// Straightforward simulation that keeps every stone, only feasible for a few blinks
+#[cfg(test)]
+mod reference {
+    pub fn count_stones(stones: &[u64], blinks: u64) -> u64 {
+        let mut stones = stones.to_vec();
+
+        for _ in 0..blinks {
+            stones = stones
+                .iter()
+                .flat_map(|stone| {
+                    let digits = stone.to_string();
+                    if *stone == 0 {
+                        vec![1]
+                    } else if digits.len() % 2 == 0 {
+                        let (a, b) = digits.split_at(digits.len() / 2);
+                        vec![a.parse().unwrap(), b.parse().unwrap()]
+                    } else {
+                        vec![stone * 2024]
+                    }
+                })
+                .collect();
+        }
+
+        stones.len() as u64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::random::Rng;
 
     const EXAMPLE: &str = include_str!("./example.txt");
 
@@ -94,4 +115,27 @@ mod tests {
     fn test1() {
         assert_eq!(solve1(EXAMPLE), 55312);
     }
+
+    #[test]
+    fn matches_reference() {
+        let mut rng = Rng::new(11);
+        let mut memory = Memo::new();
+
+        for _ in 0..50 {
+            let stones: Vec<u64> = (0..rng.range(1..4))
+                .map(|_| rng.range(0..100_000) as u64)
+                .collect();
+            let blinks = rng.range(0..16) as u64;
+
+            let memoized: u64 = stones
+                .iter()
+                .map(|stone| blink(*stone, blinks, &mut memory))
+                .sum();
+            assert_eq!(
+                memoized,
+                reference::count_stones(&stones, blinks),
+                "{stones:?} {blinks}"
+            );
+        }
+    }
 }
@@ -1,9 +1,11 @@
-use grid::{Grid, Position};
 use std::cmp::Ordering::{Greater, Less};
-use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
-fn parse_line(input: &str) -> ((i64, i64), (i64, i64)) {
+use utils::{chinese_remainder, params::Params};
+
+type Robot = ((i64, i64), (i64, i64));
+
+fn parse_line(input: &str) -> Robot {
     let (pos, v) = input.split_once(' ').unwrap();
 
     let (pos_x, pos_y) = pos[2..].split_once(',').unwrap();
@@ -39,75 +41,99 @@ fn solve1(input: &str, width: i64, height: i64) -> u64 {
     quadrants.iter().product()
 }
 
-fn solve2(input: &str) -> u64 {
-    let lines = input.lines();
+/// Returns how spread out the values are, this is the variance scaled by the amount of values squared
+fn spread(values: impl Iterator<Item = i64>) -> i64 {
+    let (count, sum, sum_squares) = values.fold((0, 0, 0), |(count, sum, sum_squares), v| {
+        (count + 1, sum + v, sum_squares + v * v)
+    });
+    count * sum_squares - sum * sum
+}
 
-    let mut robots: Vec<((i64, i64), (i64, i64))> = lines.map(parse_line).collect();
-
-    let width = 101;
-    let height = 103;
-
-    for i in 0..100000 {
-        for mid_x in 0..width {
-            let set: HashSet<(i64, i64)> = HashSet::from_iter(robots.iter().map(|(p, _)| *p));
-
-            let count = set
-                .iter()
-                .filter(|(p_x, p_y)| *p_x == mid_x || set.contains(&((mid_x * 2) - p_x - 1, *p_y)))
-                .count();
-
-            if count > (robots.len() as f32 * 0.5) as usize {
-                let mut grid = Grid::new(width as usize, height as usize, '.');
-
-                set.iter().for_each(|(p_x, p_y)| {
-                    *grid
-                        .get_mut(Position {
-                            x: *p_x as i32,
-                            y: *p_y as i32,
-                        })
-                        .unwrap() = 'X'
-                });
-                println!("Found potential tree at step {}", i);
-                grid.print();
-                return i;
-            }
-        }
+/// Returns the step within one period where the coordinate selected by `axis` is the least spread out
+fn most_clustered_step(robots: &[Robot], period: i64, axis: impl Fn(&(i64, i64)) -> i64) -> i64 {
+    (0..period)
+        .min_by_key(|step| {
+            spread(
+                robots
+                    .iter()
+                    .map(|(p, v)| (axis(p) + axis(v) * step).rem_euclid(period)),
+            )
+        })
+        .unwrap()
+}
 
-        // Step once
-        robots.iter_mut().for_each(|((p_x, p_y), (v_x, v_y))| {
-            *p_x = (((*p_x + *v_x) % width) + width) % width;
-            *p_y = (((*p_y + *v_y) % height) + height) % height;
-        });
-    }
+/// The picture shows up when the robots are clustered, the x positions repeat every `width` steps
+/// and the y positions every `height` steps. Finding the most clustered step for both axes
+/// separately and combining them with the chinese remainder theorem gives the frame.
+fn solve2(input: &str, width: i64, height: i64) -> u64 {
+    let robots: Vec<Robot> = input.lines().map(parse_line).collect();
+
+    let step_x = most_clustered_step(&robots, width, |(x, _)| *x);
+    let step_y = most_clustered_step(&robots, height, |(_, y)| *y);
 
-    0
+    chinese_remainder(&[(step_x, width), (step_y, height)])
+        .expect("Width and height should be coprime")
+        .0 as u64
 }
 
 const PUZZLE: &str = include_str!("./puzzle.txt");
 
 fn main() {
+    let params = Params::from_args();
+    let input = params.input(PUZZLE);
+    let width = params.get("width", 101);
+    let height = params.get("height", 103);
+
     let start = Instant::now();
-    println!("Result of 1: {}", solve1(PUZZLE, 101, 103));
+    println!("Result of 1: {}", solve1(&input, width, height));
     println!("Solved 1 in {:?}\n\n", start.elapsed());
 
     let start = Instant::now();
-    println!("Result of 2: {}", solve2(PUZZLE));
+    println!("Result of 2: {}", solve2(&input, width, height));
     println!("Solved 2 in {:?}", start.elapsed());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::random::Rng;
 
-    const EXAMPLE: &str = include_str!("./example.txt");
+    example_tests::example_tests! {
+        part1 = |example| {
+            let params = &example.params;
+            solve1(example.input, params.get("width", 101), params.get("height", 103))
+        };
+    }
 
-    #[test]
-    fn test1() {
-        assert_eq!(solve1(EXAMPLE, 11, 7), 12);
+    /// Plants a filled square of robots at `frame` between randomly moving robots
+    fn planted_picture(width: i64, height: i64, frame: i64) -> String {
+        let mut rng = Rng::new(0x2024);
+
+        let mut robots = Vec::new();
+        for i in 0..300 {
+            let position = if i < 200 {
+                (40 + i % 15, 30 + i / 15)
+            } else {
+                (rng.range(0..width), rng.range(0..height))
+            };
+            let velocity = (rng.range(-100..101), rng.range(-100..101));
+            robots.push((position, velocity));
+        }
+
+        robots
+            .iter()
+            .map(|((x, y), (v_x, v_y))| {
+                let start_x = (x - v_x * frame).rem_euclid(width);
+                let start_y = (y - v_y * frame).rem_euclid(height);
+                format!("p={start_x},{start_y} v={v_x},{v_y}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     #[test]
     fn test2() {
-        assert_eq!(solve2(EXAMPLE), 0);
+        assert_eq!(solve2(&planted_picture(101, 103, 6577), 101, 103), 6577);
+        assert_eq!(solve2(&planted_picture(101, 103, 42), 101, 103), 42);
     }
 }
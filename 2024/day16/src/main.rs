@@ -0,0 +1,158 @@
+use std::{
+    collections::{BinaryHeap, HashMap, HashSet},
+    time::Instant,
+};
+
+use grid::{Direction, Grid, Position};
+
+const START: char = 'S';
+const END: char = 'E';
+const WALL: char = '#';
+
+const STEP_COST: u64 = 1;
+const TURN_COST: u64 = 1000;
+
+type State = (Position, Direction);
+
+#[derive(Eq, PartialEq, Debug)]
+struct OpenState {
+    state: State,
+    score: u64,
+}
+
+impl PartialOrd for OpenState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.score.cmp(&self.score)
+    }
+}
+
+/// Returns the lowest score to reach every state from the starting states. When `reverse` is set
+/// the moves are walked backwards, which gives the lowest score from every state to the starts.
+fn lowest_scores(grid: &Grid, starts: &[State], reverse: bool) -> HashMap<State, u64> {
+    let mut scores = HashMap::new();
+    let mut open = BinaryHeap::new();
+
+    for state in starts {
+        scores.insert(*state, 0);
+        open.push(OpenState {
+            state: *state,
+            score: 0,
+        });
+    }
+
+    while let Some(OpenState {
+        state: (pos, dir),
+        score,
+    }) = open.pop()
+    {
+        if scores.get(&(pos, dir)).is_some_and(|best| score > *best) {
+            continue;
+        }
+
+        let step = if reverse {
+            pos + dir.reverse()
+        } else {
+            pos + dir
+        };
+        let mut moves = vec![
+            (pos, dir.turn_left(), score + TURN_COST),
+            (pos, dir.turn_right(), score + TURN_COST),
+        ];
+        if grid.get(step).is_some_and(|ch| ch != WALL) {
+            moves.push((step, dir, score + STEP_COST));
+        }
+
+        for (pos, dir, score) in moves {
+            if scores.get(&(pos, dir)).is_none_or(|best| score < *best) {
+                scores.insert((pos, dir), score);
+                open.push(OpenState {
+                    state: (pos, dir),
+                    score,
+                });
+            }
+        }
+    }
+
+    scores
+}
+
+fn best_score(grid: &Grid, scores: &HashMap<State, u64>) -> u64 {
+    let end = grid.find_one(END).unwrap();
+
+    Direction::all_directions()
+        .iter()
+        .filter_map(|dir| scores.get(&(end, *dir)))
+        .min()
+        .copied()
+        .expect("Should be able to reach the end")
+}
+
+fn solve1(input: &str) -> u64 {
+    let grid = Grid::from_text(input);
+    let start = grid.find_one(START).unwrap();
+
+    let scores = lowest_scores(&grid, &[(start, Direction::Right)], false);
+
+    best_score(&grid, &scores)
+}
+
+fn solve2(input: &str) -> usize {
+    let grid = Grid::from_text(input);
+    let start = grid.find_one(START).unwrap();
+    let end = grid.find_one(END).unwrap();
+
+    let from_start = lowest_scores(&grid, &[(start, Direction::Right)], false);
+    let best = best_score(&grid, &from_start);
+
+    let end_states: Vec<State> = Direction::all_directions()
+        .iter()
+        .map(|dir| (end, *dir))
+        .collect();
+    let to_end = lowest_scores(&grid, &end_states, true);
+
+    // A tile is on a best path when the score to get there plus the score to finish adds up to the best score
+    from_start
+        .iter()
+        .filter(|(state, score)| to_end.get(state).is_some_and(|rest| **score + rest == best))
+        .map(|((pos, _), _)| *pos)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+const PUZZLE: &str = include_str!("./puzzle.txt");
+
+fn main() {
+    let start = Instant::now();
+    println!("Result of 1: {}", solve1(PUZZLE));
+    println!("Solved 1 in {:?}\n\n", start.elapsed());
+
+    let start = Instant::now();
+    println!("Result of 2: {}", solve2(PUZZLE));
+    println!("Solved 2 in {:?}", start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("./example.txt");
+    const EXAMPLE2: &str = include_str!("./example2.txt");
+
+    #[test]
+    fn test1() {
+        assert_eq!(solve1(EXAMPLE), 7036);
+        assert_eq!(solve1(EXAMPLE2), 11048);
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(solve2(EXAMPLE), 45);
+        assert_eq!(solve2(EXAMPLE2), 64);
+    }
+}
@@ -2,6 +2,7 @@ use std::time::Instant;
 
 use grid::bfs::bfs_search;
 use grid::{Grid, Position};
+use utils::params::Params;
 
 const EMPTY: char = '.';
 
@@ -71,14 +72,21 @@ fn solve2(input: &str, grid: Grid) -> String {
 const PUZZLE: &str = include_str!("./puzzle.txt");
 
 fn main() {
+    let params = Params::from_args();
+    let input = params.input(PUZZLE);
+    let size = params.get("size", 71);
+
     let start = Instant::now();
-    let grid = Grid::new(71, 71, EMPTY);
-    println!("Result of 1: {}", solve1(PUZZLE, grid, 1024));
+    let grid = Grid::new(size, size, EMPTY);
+    println!(
+        "Result of 1: {}",
+        solve1(&input, grid, params.get("steps", 1024))
+    );
     println!("Solved 1 in {:?}\n\n", start.elapsed());
 
     let start = Instant::now();
-    let grid = Grid::new(71, 71, EMPTY);
-    println!("Result of 2: {}", solve2(PUZZLE, grid));
+    let grid = Grid::new(size, size, EMPTY);
+    println!("Result of 2: {}", solve2(&input, grid));
     println!("Solved 2 in {:?}", start.elapsed());
 }
 
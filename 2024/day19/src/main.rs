@@ -0,0 +1,73 @@
+use std::time::Instant;
+
+use utils::memo::Memo;
+
+fn parse_input(input: &str) -> (Vec<&str>, Vec<&str>) {
+    let (towels, designs) = input
+        .split_once("\n\n")
+        .expect("Towels and designs should be separated by an empty line");
+    (towels.split(", ").collect(), designs.lines().collect())
+}
+
+/// Counts the ways the design can be made from the towels, the memo is keyed on the length of the
+/// remaining design since that is always a suffix of the same design
+fn count_arrangements(design: &str, towels: &[&str], memo: &mut Memo<usize, u64>) -> u64 {
+    if design.is_empty() {
+        return 1;
+    }
+
+    memo.get_or_compute(design.len(), |memo| {
+        towels
+            .iter()
+            .filter_map(|towel| design.strip_prefix(towel))
+            .map(|rest| count_arrangements(rest, towels, memo))
+            .sum()
+    })
+}
+
+fn solve1(input: &str) -> usize {
+    let (towels, designs) = parse_input(input);
+
+    designs
+        .iter()
+        .filter(|design| count_arrangements(design, &towels, &mut Memo::new()) > 0)
+        .count()
+}
+
+fn solve2(input: &str) -> u64 {
+    let (towels, designs) = parse_input(input);
+
+    designs
+        .iter()
+        .map(|design| count_arrangements(design, &towels, &mut Memo::new()))
+        .sum()
+}
+
+const PUZZLE: &str = include_str!("./puzzle.txt");
+
+fn main() {
+    let start = Instant::now();
+    println!("Result of 1: {}", solve1(PUZZLE));
+    println!("Solved 1 in {:?}\n\n", start.elapsed());
+
+    let start = Instant::now();
+    println!("Result of 2: {}", solve2(PUZZLE));
+    println!("Solved 2 in {:?}", start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("./example.txt");
+
+    #[test]
+    fn test1() {
+        assert_eq!(solve1(EXAMPLE), 6);
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(solve2(EXAMPLE), 16);
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Instant;
+
+const PRUNE: u64 = 16777216;
+const SECRETS_PER_DAY: usize = 2000;
+
+// Price changes are between -9 and 9, so a window of four changes fits in 19^4 slots
+const CHANGE_OPTIONS: usize = 19;
+const WINDOW_SLOTS: usize = CHANGE_OPTIONS.pow(4);
+
+fn next_secret(mut secret: u64) -> u64 {
+    secret = ((secret * 64) ^ secret) % PRUNE;
+    secret = ((secret / 32) ^ secret) % PRUNE;
+    ((secret * 2048) ^ secret) % PRUNE
+}
+
+fn secrets(initial: u64) -> impl Iterator<Item = u64> {
+    std::iter::successors(Some(initial), |secret| Some(next_secret(*secret)))
+}
+
+fn parse_input(input: &str) -> Vec<u64> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+fn solve1(input: &str) -> u64 {
+    parse_input(input)
+        .iter()
+        .map(|initial| secrets(*initial).nth(SECRETS_PER_DAY).unwrap())
+        .sum()
+}
+
+fn solve2(input: &str) -> u64 {
+    let buyers = parse_input(input);
+
+    // Total bananas for every window of four changes, a buyer only sells at the first occurrence
+    let mut bananas = vec![0u64; WINDOW_SLOTS];
+    let mut last_buyer = vec![usize::MAX; WINDOW_SLOTS];
+
+    for (buyer, initial) in buyers.iter().enumerate() {
+        let prices: Vec<i64> = secrets(*initial)
+            .take(SECRETS_PER_DAY + 1)
+            .map(|secret| (secret % 10) as i64)
+            .collect();
+
+        for window in prices.windows(5) {
+            let slot = window.windows(2).fold(0, |slot, pair| {
+                slot * CHANGE_OPTIONS + (pair[1] - pair[0] + 9) as usize
+            });
+
+            if last_buyer[slot] != buyer {
+                last_buyer[slot] = buyer;
+                bananas[slot] += window[4] as u64;
+            }
+        }
+    }
+
+    bananas.into_iter().max().unwrap()
+}
+
+const PUZZLE: &str = include_str!("./puzzle.txt");
+
+fn main() {
+    let start = Instant::now();
+    println!("Result of 1: {}", solve1(PUZZLE));
+    println!("Solved 1 in {:?}\n\n", start.elapsed());
+
+    let start = Instant::now();
+    println!("Result of 2: {}", solve2(PUZZLE));
+    println!("Solved 2 in {:?}", start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("./example.txt");
+    const EXAMPLE2: &str = include_str!("./example2.txt");
+
+    #[test]
+    fn secret_sequence() {
+        let sequence: Vec<u64> = secrets(123).skip(1).take(3).collect();
+        assert_eq!(sequence, [15887950, 16495136, 527345]);
+    }
+
+    #[test]
+    fn test1() {
+        assert_eq!(solve1(EXAMPLE), 37327623);
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(solve2(EXAMPLE2), 23);
+    }
+}
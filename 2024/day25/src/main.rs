@@ -0,0 +1,63 @@
+use std::time::Instant;
+
+const FILLED: char = '#';
+const HEIGHT: usize = 5;
+
+enum Schematic {
+    Lock([usize; 5]),
+    Key([usize; 5]),
+}
+
+fn parse_schematic(input: &str) -> Schematic {
+    let mut heights = [0; 5];
+    for line in input.lines().skip(1).take(HEIGHT) {
+        line.chars()
+            .zip(heights.iter_mut())
+            .filter(|(ch, _)| *ch == FILLED)
+            .for_each(|(_, height)| *height += 1);
+    }
+
+    if input.starts_with(FILLED) {
+        Schematic::Lock(heights)
+    } else {
+        Schematic::Key(heights)
+    }
+}
+
+fn solve1(input: &str) -> usize {
+    let mut locks = Vec::new();
+    let mut keys = Vec::new();
+
+    for schematic in input.split("\n\n").map(parse_schematic) {
+        match schematic {
+            Schematic::Lock(heights) => locks.push(heights),
+            Schematic::Key(heights) => keys.push(heights),
+        }
+    }
+
+    locks
+        .iter()
+        .flat_map(|lock| keys.iter().map(move |key| (lock, key)))
+        .filter(|(lock, key)| lock.iter().zip(key.iter()).all(|(a, b)| a + b <= HEIGHT))
+        .count()
+}
+
+const PUZZLE: &str = include_str!("./puzzle.txt");
+
+fn main() {
+    let start = Instant::now();
+    println!("Result of 1: {}", solve1(PUZZLE));
+    println!("Solved 1 in {:?}", start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("./example.txt");
+
+    #[test]
+    fn test1() {
+        assert_eq!(solve1(EXAMPLE), 3);
+    }
+}
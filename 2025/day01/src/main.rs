@@ -77,9 +77,38 @@ fn main() {
     println!("Solved 2 in {:?}", start.elapsed());
 }
 
+/// Turns the dial one click at a time
+#[cfg(test)]
+mod reference {
+    /// Returns how often the dial ends a rotation at zero and how often it passes zero at all
+    pub fn count_zeros(input: &str) -> (u64, u64) {
+        let mut pos = 50;
+        let mut ends_at_zero = 0;
+        let mut passes_zero = 0;
+
+        for line in input.lines() {
+            let (dir, clicks) = line.split_at(1);
+            let step = if dir == "L" { 99 } else { 1 };
+
+            for _ in 0..clicks.parse::<u32>().unwrap() {
+                pos = (pos + step) % 100;
+                if pos == 0 {
+                    passes_zero += 1;
+                }
+            }
+            if pos == 0 {
+                ends_at_zero += 1;
+            }
+        }
+
+        (ends_at_zero, passes_zero)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::random::Rng;
 
     const EXAMPLE: &str = include_str!("./example.txt");
 
@@ -102,4 +131,25 @@ mod tests {
         assert_eq!(solve2("L450"), 5);
         assert_eq!(solve2("L450\nR550"), 10);
     }
+
+    #[test]
+    fn matches_reference() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..500 {
+            let lines: Vec<String> = (0..rng.range(1..20))
+                .map(|_| {
+                    let dir = if rng.chance(0.5) { 'L' } else { 'R' };
+                    // Small rotations make landing exactly on zero common
+                    let max = if rng.chance(0.5) { 60 } else { 400 };
+                    format!("{dir}{}", rng.range(1..max))
+                })
+                .collect();
+            let input = lines.join("\n");
+
+            let (ends_at_zero, passes_zero) = reference::count_zeros(&input);
+            assert_eq!(solve1(&input), ends_at_zero, "{input}");
+            assert_eq!(solve2(&input), passes_zero, "{input}");
+        }
+    }
 }
@@ -1,106 +1,71 @@
-#![feature(ascii_char)]
+use std::{ops::Range, time::Instant};
 
-use std::{ascii, time::Instant};
+use grid::Grid;
 
-fn get_column_indices(input: &str) -> Vec<usize> {
-    let lines: Vec<&str> = input.lines().collect();
+/// Returns the column ranges of the problems, problems are separated by columns of only spaces
+fn get_problems(grid: &Grid) -> Vec<Range<usize>> {
+    let mut problems = Vec::new();
+    let mut start = 0;
 
-    let mut output = Vec::new();
-
-    for idx in 0..lines[0].len() {
-        if lines.iter().all(|line| line.as_bytes()[idx] == b' ') {
-            output.push(idx);
+    for x in 0..=grid.width() {
+        let separator = x == grid.width() || grid.iter_rows().all(|(_, row)| row[x] == ' ');
+        if separator {
+            if start < x {
+                problems.push(start..x);
+            }
+            start = x + 1;
         }
     }
 
-    output
+    problems
 }
 
-fn get_columns(input: &str) -> Vec<Vec<&str>> {
-    let indices = get_column_indices(input);
-
-    let lines: Vec<&str> = input.lines().collect();
+fn calculate(grid: &Grid, get_numbers: impl Fn(&[&[char]], Range<usize>) -> Vec<u64>) -> u64 {
+    let rows: Vec<&[char]> = grid.iter_rows().map(|(_, row)| row).collect();
+    let (ops, numbers) = rows.split_last().unwrap();
 
-    let mut columns = Vec::new();
-    columns.resize(indices.len() + 1, Vec::new());
+    get_problems(grid)
+        .into_iter()
+        .map(|columns| {
+            let numbers = get_numbers(numbers, columns.clone()).into_iter();
 
-    for mut line in lines {
-        for (idx, pos) in indices.iter().rev().enumerate() {
-            let (remainder, part) = line.split_at(*pos);
-            line = remainder;
-            columns[idx].push(part);
-        }
-        let (part, _) = line.split_at(*indices.first().unwrap());
-        columns.last_mut().unwrap().push(part);
-    }
+            match ops[columns].iter().find(|ch| **ch != ' ') {
+                Some('+') => numbers.sum(),
+                Some('*') => numbers.product(),
+                _ => 0,
+            }
+        })
+        .sum()
+}
 
-    columns
+fn parse_number(digits: impl Iterator<Item = char>) -> u64 {
+    digits
+        .filter(|ch| *ch != ' ')
+        .collect::<String>()
+        .parse()
+        .unwrap()
 }
 
 fn solve1(input: &str) -> u64 {
-    let mut total = 0;
-    let columns = get_columns(input);
-
-    for column in columns {
-        let (op, numbers) = column.as_slice().split_last().unwrap();
-
-        let numbers = numbers.iter().map(|nr| nr.trim().parse::<u64>().unwrap());
-
-        total += match op.trim() {
-            "+" => numbers.sum(),
-            "*" => numbers.product(),
-            _ => 0,
-        };
-    }
-
-    total
+    let grid = Grid::from_text_padded(input, ' ');
+
+    // Every row holds a number
+    calculate(&grid, |rows, columns| {
+        rows.iter()
+            .map(|row| parse_number(row[columns.clone()].iter().copied()))
+            .collect()
+    })
 }
 
 fn solve2(input: &str) -> u64 {
-    let lines: Vec<&[ascii::Char]> = input.lines().map(|line| line.as_ascii().unwrap()).collect();
-
-    let mut input = Vec::with_capacity(input.len());
-
-    for idx in 0..lines[0].len() {
-        for line in lines.iter().rev() {
-            input.push(line[idx]);
-        }
-    }
-
-    let mut total = 0;
-    let mut subtotal = 0;
-    let mut current_value = 0;
-    let mut current_decimal = 1;
-    let mut op = ' ';
-
-    for ch in input {
-        match ch.to_char() {
-            '*' | '+' => {
-                total += subtotal;
-                subtotal = 0;
-                op = ch.to_char();
-            }
-            ' ' if current_value != 0 => {
-                if op == '+' || subtotal == 0 {
-                    subtotal += current_value;
-                } else if op == '*' {
-                    subtotal *= current_value;
-                }
-                current_value = 0;
-                current_decimal = 1;
-            }
-            x if x.is_ascii_digit() => {
-                current_value += x.to_digit(10).unwrap() as u64 * current_decimal;
-                current_decimal *= 10;
-            }
-            _ => (),
-        }
-    }
-
-    // Add last one as it does not have an operator
-    total += subtotal;
-
-    total
+    let grid = Grid::from_text_padded(input, ' ');
+
+    // Every column holds a number, written top to bottom
+    calculate(&grid, |rows, columns| {
+        columns
+            .map(|x| parse_number(rows.iter().map(|row| row[x])))
+            .collect()
+    })
 }
 
 const PUZZLE: &str = include_str!("./puzzle.txt");
@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use proc_macro::{TokenStream, TokenTree};
+
+/// Generates a test for every answer listed next to the example inputs in `src/`
+///
+/// Every `src/example*.answers` file belongs to the `.txt` file with the same name and holds
+/// `name = value` lines. The `part1` and `part2` lines are the expected answers, all other lines
+/// are parameters. Lines starting with `#` are comments.
+///
+/// ```text
+/// # src/example2.answers
+/// part1 = 12
+/// width = 11
+/// ```
+///
+/// The macro takes a solver per part, which gets a `utils::examples::Example` and returns
+/// anything that can be displayed. The solvers are separated by semicolons:
+///
+/// ```ignore
+/// example_tests::example_tests! {
+///     part1 = |example| solve1(example.input, example.params.get("width", 101));
+///     part2 = |example| solve2(example.input);
+/// }
+/// ```
+///
+/// This generates `example2_part1` and so on, a test whose input file is missing is ignored. The
+/// files are read when the crate is compiled, so a newly added answers file is only picked up
+/// after the test module changes or the crate is rebuilt.
+///
+/// The generated code refers to `::utils::examples`, so a crate using the macro needs `utils` as
+/// a dependency of its own, next to `example_tests`.
+#[proc_macro]
+pub fn example_tests(input: TokenStream) -> TokenStream {
+    match generate(input) {
+        Ok(output) => output,
+        Err(message) => format!("compile_error!({message:?});").parse().unwrap(),
+    }
+}
+
+fn generate(input: TokenStream) -> Result<TokenStream, String> {
+    let solvers = parse_solvers(input)?;
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|error| error.to_string())?;
+    let src = PathBuf::from(manifest_dir).join("src");
+
+    let mut answer_files: Vec<PathBuf> = fs::read_dir(&src)
+        .map_err(|error| format!("Can't read {}: {error}", src.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "answers")
+                && file_stem(path).starts_with("example")
+        })
+        .collect();
+    answer_files.sort();
+
+    let mut output = String::new();
+    for answers_path in answer_files {
+        let answers = parse_answers(&answers_path)?;
+        let input_path = answers_path.with_extension("txt");
+        let name = file_stem(&answers_path);
+        let test_name: String = name
+            .chars()
+            .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+            .collect();
+
+        let params: Vec<String> = answers
+            .iter()
+            .filter(|(key, _)| !solvers.iter().any(|(part, _)| part == key))
+            .flat_map(|(key, value)| ["--param".to_string(), format!("{key}={value}")])
+            .collect();
+
+        for (part, solver) in &solvers {
+            let Some((_, expected)) = answers.iter().find(|(key, _)| key == part) else {
+                continue;
+            };
+
+            if !input_path.exists() {
+                output += &format!(
+                    "#[test] #[ignore = \"{name}.txt is missing\"] fn {test_name}_{part}() {{}}\n"
+                );
+                continue;
+            }
+
+            output += &format!(
+                r#"
+                #[test]
+                fn {test_name}_{part}() {{
+                    const _: &str = include_str!({answers_path:?});
+                    let args: [&str; {count}] = {params:?};
+                    let example = ::utils::examples::Example {{
+                        name: {name:?},
+                        path: {input_path:?},
+                        input: include_str!({input_path:?}),
+                        params: ::utils::params::Params::parse(args).unwrap(),
+                    }};
+                    ::utils::examples::check(&example, {expected:?}, {solver});
+                }}
+                "#,
+                answers_path = answers_path.display().to_string(),
+                input_path = input_path.display().to_string(),
+                count = params.len(),
+            );
+        }
+    }
+
+    output.parse().map_err(|error| format!("{error:?}"))
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Parses `part = solver; part = solver` where a solver ends at a semicolon outside of brackets
+fn parse_solvers(input: TokenStream) -> Result<Vec<(String, String)>, String> {
+    let mut solvers = Vec::new();
+    let mut tokens = input.into_iter();
+
+    while let Some(token) = tokens.next() {
+        let TokenTree::Ident(part) = token else {
+            return Err(format!("Expected a part name, got {token}"));
+        };
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => (),
+            _ => return Err(format!("Expected = after {part}")),
+        }
+
+        let mut solver = TokenStream::new();
+        for token in tokens.by_ref() {
+            if matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ';') {
+                break;
+            }
+            solver.extend([token]);
+        }
+        if solver.is_empty() {
+            return Err(format!("Missing solver for {part}"));
+        }
+
+        solvers.push((part.to_string(), solver.to_string()));
+    }
+
+    Ok(solvers)
+}
+
+fn parse_answers(path: &Path) -> Result<Vec<(String, String)>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Can't read {}: {error}", path.display()))?;
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split_once('=')
+                .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                .ok_or(format!(
+                    "Expected name = value in {}: {line}",
+                    path.display()
+                ))
+        })
+        .collect()
+}
@@ -0,0 +1,122 @@
+pub mod longest_path;
+pub mod partial_order;
+pub mod search;
+pub mod topological;
+
+use std::collections::HashMap;
+
+pub type NodeId = usize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub to: NodeId,
+    pub weight: u64,
+}
+
+/// Directed graph stored as adjacency lists, nodes are referred to by their interned name or
+/// by the `NodeId` handed out when the name was first seen
+///
+/// # Example
+///
+/// ```
+/// use graph::Graph;
+///
+/// let mut graph = Graph::new();
+/// graph.add_edge("AAA", "BBB", 1);
+/// graph.add_edge("AAA", "CCC", 5);
+///
+/// let aaa = graph.id("AAA").unwrap();
+/// assert_eq!(graph.len(), 3);
+/// assert_eq!(graph.name(graph.neighbors(aaa)[1].to), "CCC");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<Edge>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a graph with `len` unnamed nodes, for when the ids are all that is needed. The
+    /// names of these nodes are empty and can't be looked up.
+    pub fn with_nodes(len: usize) -> Self {
+        Self {
+            names: vec![String::new(); len],
+            ids: HashMap::new(),
+            edges: vec![Vec::new(); len],
+        }
+    }
+
+    /// Returns the id of the node with the name, adding the node if it doesn't exist yet
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + use<> {
+        0..self.names.len()
+    }
+
+    /// Adds a directed edge, edges keep the order in which they are added
+    pub fn add_edge(&mut self, from: &str, to: &str, weight: u64) {
+        let from = self.node(from);
+        let to = self.node(to);
+        self.add_edge_ids(from, to, weight);
+    }
+
+    pub fn add_undirected_edge(&mut self, a: &str, b: &str, weight: u64) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn add_edge_ids(&mut self, from: NodeId, to: NodeId, weight: u64) {
+        self.edges[from].push(Edge { to, weight });
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> &[Edge] {
+        &self.edges[id]
+    }
+
+    /// Returns the graph with all edges pointing the other way
+    pub fn reversed(&self) -> Self {
+        let mut reversed = Self {
+            names: self.names.clone(),
+            ids: self.ids.clone(),
+            edges: vec![Vec::new(); self.names.len()],
+        };
+
+        for from in self.nodes() {
+            for edge in self.neighbors(from) {
+                reversed.add_edge_ids(edge.to, from, edge.weight);
+            }
+        }
+        reversed
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{Graph, NodeId};
+
+/// Returns the highest total weight of a path from the start to the goal that visits every node at
+/// most once. This is an exhaustive depth first search with the visited nodes kept in a bitmask, so
+/// it is only feasible on small (compressed) graphs and supports at most 64 nodes.
+///
+/// # Example
+///
+/// ```
+/// use graph::{Graph, longest_path::longest_simple_path};
+///
+/// let mut graph = Graph::new();
+/// graph.add_undirected_edge("start", "a", 1);
+/// graph.add_undirected_edge("start", "b", 10);
+/// graph.add_undirected_edge("a", "b", 1);
+/// graph.add_undirected_edge("a", "end", 1);
+/// graph.add_undirected_edge("b", "end", 1);
+///
+/// let start = graph.id("start").unwrap();
+/// let end = graph.id("end").unwrap();
+/// assert_eq!(longest_simple_path(&graph, start, end), Some(12));
+/// ```
+pub fn longest_simple_path(graph: &Graph, start: NodeId, goal: NodeId) -> Option<u64> {
+    assert!(
+        graph.len() <= 64,
+        "Only graphs up to 64 nodes are supported"
+    );
+
+    search(graph, start, goal, 1 << start)
+}
+
+fn search(graph: &Graph, node: NodeId, goal: NodeId, visited: u64) -> Option<u64> {
+    if node == goal {
+        return Some(0);
+    }
+
+    graph
+        .neighbors(node)
+        .iter()
+        .filter(|edge| visited & (1 << edge.to) == 0)
+        .filter_map(|edge| {
+            search(graph, edge.to, goal, visited | (1 << edge.to)).map(|rest| rest + edge.weight)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreachable() {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b", 3);
+        graph.add_edge("c", "a", 3);
+
+        let a = graph.id("a").unwrap();
+        let b = graph.id("b").unwrap();
+        let c = graph.id("c").unwrap();
+        assert_eq!(longest_simple_path(&graph, a, b), Some(3));
+        assert_eq!(longest_simple_path(&graph, c, b), Some(6));
+        assert_eq!(longest_simple_path(&graph, b, c), None);
+        assert_eq!(longest_simple_path(&graph, a, a), Some(0));
+    }
+}
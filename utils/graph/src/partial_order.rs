@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+use crate::Graph;
+use crate::topological::{strongly_connected_components, topological_sort};
+
+/// The items that could not be ordered because the rules between them form a cycle, items that
+/// only come before or after a cycle are not included
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contradiction<T> {
+    pub items: Vec<T>,
+}
+
+impl<T: Display> Display for Contradiction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rules contradict each other for")?;
+        for item in &self.items {
+            write!(f, " {item}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug + Display> std::error::Error for Contradiction<T> {}
+
+/// A set of "comes before" rules that only have to hold between items that are both present
+///
+/// # Example
+///
+/// ```
+/// use graph::partial_order::PartialOrder;
+///
+/// let rules: PartialOrder<u32> = [(47, 53), (97, 13), (97, 47), (75, 53), (47, 13)]
+///     .into_iter()
+///     .collect();
+///
+/// assert!(rules.is_ordered(&[97, 47, 13]));
+/// assert!(!rules.is_ordered(&[53, 75]));
+/// assert_eq!(rules.order(&[13, 47, 97, 53]).unwrap(), [97, 47, 13, 53]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PartialOrder<T> {
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T> Default for PartialOrder<T> {
+    fn default() -> Self {
+        Self {
+            successors: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> PartialOrder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_rule(&mut self, before: T, after: T) {
+        self.successors.entry(before).or_default().insert(after);
+    }
+
+    /// Returns true when a rule says `a` has to come before `b`
+    pub fn precedes(&self, a: &T, b: &T) -> bool {
+        self.successors
+            .get(a)
+            .is_some_and(|successors| successors.contains(b))
+    }
+
+    /// Returns true when no item comes after an item it should precede
+    pub fn is_ordered(&self, sequence: &[T]) -> bool {
+        sequence.iter().enumerate().all(|(i, item)| {
+            sequence[..i]
+                .iter()
+                .all(|earlier| !self.precedes(item, earlier))
+        })
+    }
+
+    /// Orders the items so every rule between them holds. Of the items whose predecessors are
+    /// already placed, the one with the lowest input index comes next, so the result is
+    /// deterministic.
+    pub fn order(&self, items: &[T]) -> Result<Vec<T>, Contradiction<T>> {
+        // Node ids are the indices of the items, so the lowest id first tie break of the
+        // topological sort picks the earliest available item
+        let mut graph = Graph::with_nodes(items.len());
+
+        for (i, a) in items.iter().enumerate() {
+            for (j, b) in items.iter().enumerate() {
+                if i != j && self.precedes(a, b) {
+                    graph.add_edge_ids(i, j, 1);
+                }
+            }
+        }
+
+        match topological_sort(&graph) {
+            Ok(order) => Ok(order.into_iter().map(|id| items[id].clone()).collect()),
+            Err(_) => {
+                // The sort also gives up on everything after a cycle, only the components that
+                // loop back on themselves actually contradict each other
+                let mut ids: Vec<usize> = strongly_connected_components(&graph)
+                    .into_iter()
+                    .filter(|component| component.len() > 1)
+                    .flatten()
+                    .collect();
+                ids.sort();
+
+                Err(Contradiction {
+                    items: ids.into_iter().map(|id| items[id].clone()).collect(),
+                })
+            }
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> FromIterator<(T, T)> for PartialOrder<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut order = Self::new();
+        for (before, after) in iter {
+            order.add_rule(before, after);
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contradiction() {
+        let rules: PartialOrder<&str> = [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")]
+            .into_iter()
+            .collect();
+
+        let error = rules.order(&["d", "c", "x", "b", "a"]).unwrap_err();
+        assert_eq!(error.items, ["c", "b", "a"]);
+        assert_eq!(error.to_string(), "Rules contradict each other for c b a");
+
+        // Without the item closing the cycle the rules are fine
+        assert_eq!(rules.order(&["d", "c", "b"]).unwrap(), ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn lowest_available_index_first() {
+        let rules: PartialOrder<&str> = [("c", "a")].into_iter().collect();
+
+        // `a` has to wait for `c`, so `b` moves ahead of it
+        assert_eq!(rules.order(&["a", "b", "c"]).unwrap(), ["b", "c", "a"]);
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::{Graph, NodeId};
+
+/// Returns the amount of edges on the shortest route from the start to every node, ignoring weights
+pub fn bfs(graph: &Graph, start: NodeId) -> Vec<Option<usize>> {
+    let mut distances = vec![None; graph.len()];
+    let mut open = VecDeque::new();
+
+    distances[start] = Some(0);
+    open.push_back(start);
+
+    while let Some(node) = open.pop_front() {
+        let cost = distances[node].unwrap();
+        for edge in graph.neighbors(node) {
+            if distances[edge.to].is_none() {
+                distances[edge.to] = Some(cost + 1);
+                open.push_back(edge.to);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Returns the lowest total weight from the start to every node
+pub fn dijkstra(graph: &Graph, start: NodeId) -> Vec<Option<u64>> {
+    search(graph, start, None).0
+}
+
+/// Returns the lowest total weight from the start to the goal and the nodes on that route
+///
+/// # Example
+///
+/// ```
+/// use graph::{Graph, search::shortest_path};
+///
+/// let mut graph = Graph::new();
+/// graph.add_edge("a", "b", 1);
+/// graph.add_edge("b", "d", 1);
+/// graph.add_edge("a", "c", 1);
+/// graph.add_edge("c", "d", 5);
+/// graph.add_edge("a", "d", 4);
+///
+/// let (cost, path) = shortest_path(&graph, graph.id("a").unwrap(), graph.id("d").unwrap()).unwrap();
+/// assert_eq!(cost, 2);
+/// assert_eq!(path.iter().map(|id| graph.name(*id)).collect::<Vec<_>>(), ["a", "b", "d"]);
+/// ```
+pub fn shortest_path(graph: &Graph, start: NodeId, goal: NodeId) -> Option<(u64, Vec<NodeId>)> {
+    let (distances, came_from) = search(graph, start, Some(goal));
+    let cost = distances[goal]?;
+
+    let mut path = vec![goal];
+    let mut current = goal;
+    while let Some(previous) = came_from[current] {
+        current = previous;
+        path.push(current);
+    }
+    path.reverse();
+
+    Some((cost, path))
+}
+
+fn search(
+    graph: &Graph,
+    start: NodeId,
+    goal: Option<NodeId>,
+) -> (Vec<Option<u64>>, Vec<Option<NodeId>>) {
+    let mut distances = vec![None; graph.len()];
+    let mut came_from = vec![None; graph.len()];
+    let mut done = vec![false; graph.len()];
+    let mut open = BinaryHeap::new();
+
+    distances[start] = Some(0);
+    open.push(Reverse((0, start)));
+
+    while let Some(Reverse((cost, node))) = open.pop() {
+        if done[node] {
+            continue;
+        }
+        done[node] = true;
+
+        if Some(node) == goal {
+            break;
+        }
+
+        for edge in graph.neighbors(node) {
+            let tentative = cost + edge.weight;
+            if distances[edge.to].is_none_or(|distance| tentative < distance) {
+                distances[edge.to] = Some(tentative);
+                came_from[edge.to] = Some(node);
+                open.push(Reverse((tentative, edge.to)));
+            }
+        }
+    }
+
+    (distances, came_from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Graph {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b", 7);
+        graph.add_edge("a", "c", 9);
+        graph.add_edge("a", "f", 14);
+        graph.add_edge("b", "c", 10);
+        graph.add_edge("b", "d", 15);
+        graph.add_edge("c", "d", 11);
+        graph.add_edge("c", "f", 2);
+        graph.add_edge("d", "e", 6);
+        graph.add_edge("f", "e", 9);
+        graph.node("unreachable");
+        graph
+    }
+
+    #[test]
+    fn distances() {
+        let graph = example();
+        let a = graph.id("a").unwrap();
+
+        let weighted = dijkstra(&graph, a);
+        assert_eq!(weighted[graph.id("e").unwrap()], Some(20));
+        assert_eq!(weighted[graph.id("d").unwrap()], Some(20));
+        assert_eq!(weighted[graph.id("unreachable").unwrap()], None);
+
+        let hops = bfs(&graph, a);
+        assert_eq!(hops[graph.id("e").unwrap()], Some(2));
+        assert_eq!(hops[graph.id("unreachable").unwrap()], None);
+    }
+
+    #[test]
+    fn path() {
+        let graph = example();
+        let (cost, path) =
+            shortest_path(&graph, graph.id("a").unwrap(), graph.id("e").unwrap()).unwrap();
+
+        assert_eq!(cost, 20);
+        assert_eq!(
+            path.iter().map(|id| graph.name(*id)).collect::<Vec<_>>(),
+            ["a", "c", "f", "e"]
+        );
+        assert_eq!(
+            shortest_path(&graph, graph.id("e").unwrap(), graph.id("a").unwrap()),
+            None
+        );
+    }
+}
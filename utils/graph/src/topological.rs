@@ -0,0 +1,173 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::{Graph, NodeId};
+
+/// The nodes that could not be ordered because they are part of or depend on a cycle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub nodes: Vec<NodeId>,
+}
+
+/// Orders the nodes so every edge points forward using Kahn's algorithm, when multiple nodes are
+/// available the lowest id goes first so the order is deterministic
+///
+/// # Example
+///
+/// ```
+/// use graph::{Graph, topological::topological_sort};
+///
+/// let mut graph = Graph::new();
+/// graph.add_edge("shirt", "tie", 1);
+/// graph.add_edge("tie", "jacket", 1);
+/// graph.add_edge("trousers", "shoes", 1);
+/// graph.add_edge("trousers", "jacket", 1);
+///
+/// let order = topological_sort(&graph).unwrap();
+/// let names: Vec<_> = order.iter().map(|id| graph.name(*id)).collect();
+/// assert_eq!(names, ["shirt", "tie", "trousers", "jacket", "shoes"]);
+///
+/// graph.add_edge("jacket", "shirt", 1);
+/// assert_eq!(topological_sort(&graph).unwrap_err().nodes.len(), 3);
+/// ```
+pub fn topological_sort(graph: &Graph) -> Result<Vec<NodeId>, Cycle> {
+    let mut incoming = vec![0; graph.len()];
+    for node in graph.nodes() {
+        for edge in graph.neighbors(node) {
+            incoming[edge.to] += 1;
+        }
+    }
+
+    let mut available: BinaryHeap<_> = graph
+        .nodes()
+        .filter(|node| incoming[*node] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(graph.len());
+
+    while let Some(Reverse(node)) = available.pop() {
+        order.push(node);
+        for edge in graph.neighbors(node) {
+            incoming[edge.to] -= 1;
+            if incoming[edge.to] == 0 {
+                available.push(Reverse(edge.to));
+            }
+        }
+    }
+
+    if order.len() == graph.len() {
+        Ok(order)
+    } else {
+        Err(Cycle {
+            nodes: graph.nodes().filter(|node| incoming[*node] > 0).collect(),
+        })
+    }
+}
+
+/// Returns the strongly connected components using Kosaraju's algorithm, components are in
+/// topological order and the nodes within a component are sorted
+pub fn strongly_connected_components(graph: &Graph) -> Vec<Vec<NodeId>> {
+    // First pass: order the nodes by the time their depth first search finishes
+    let mut visited = vec![false; graph.len()];
+    let mut finished = Vec::with_capacity(graph.len());
+
+    for root in graph.nodes() {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut stack = vec![(root, 0)];
+
+        while let Some((node, next_edge)) = stack.pop() {
+            if let Some(edge) = graph.neighbors(node).get(next_edge) {
+                stack.push((node, next_edge + 1));
+                if !visited[edge.to] {
+                    visited[edge.to] = true;
+                    stack.push((edge.to, 0));
+                }
+            } else {
+                finished.push(node);
+            }
+        }
+    }
+
+    // Second pass: every search on the reversed graph in reverse finishing order finds one component
+    let reversed = graph.reversed();
+    let mut assigned = vec![false; graph.len()];
+    let mut components = Vec::new();
+
+    for root in finished.into_iter().rev() {
+        if assigned[root] {
+            continue;
+        }
+        assigned[root] = true;
+        let mut component = Vec::new();
+        let mut stack = vec![root];
+
+        while let Some(node) = stack.pop() {
+            component.push(node);
+            for edge in reversed.neighbors(node) {
+                if !assigned[edge.to] {
+                    assigned[edge.to] = true;
+                    stack.push(edge.to);
+                }
+            }
+        }
+
+        component.sort_unstable();
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components() {
+        let mut graph = Graph::new();
+        for (from, to) in [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("b", "d"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+            ("g", "f"),
+            ("g", "h"),
+            ("h", "g"),
+        ] {
+            graph.add_edge(from, to, 1);
+        }
+
+        let mut components: Vec<Vec<&str>> = strongly_connected_components(&graph)
+            .iter()
+            .map(|component| component.iter().map(|id| graph.name(*id)).collect())
+            .collect();
+        let d_component = components.iter().position(|c| c.contains(&"d")).unwrap();
+        let a_component = components.iter().position(|c| c.contains(&"a")).unwrap();
+        assert!(a_component < d_component);
+
+        components.sort();
+        assert_eq!(
+            components,
+            vec![vec!["a", "b", "c"], vec!["d", "e", "f"], vec!["g", "h"]]
+        );
+    }
+
+    #[test]
+    fn cycle_includes_dependents() {
+        let mut graph = Graph::new();
+        graph.add_edge("start", "a", 1);
+        graph.add_edge("a", "b", 1);
+        graph.add_edge("b", "a", 1);
+        graph.add_edge("b", "after", 1);
+
+        let cycle = topological_sort(&graph).unwrap_err();
+        let names: Vec<_> = cycle.nodes.iter().map(|id| graph.name(*id)).collect();
+        assert_eq!(names, ["a", "b", "after"]);
+    }
+}
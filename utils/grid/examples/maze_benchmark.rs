@@ -0,0 +1,155 @@
+//! Compares the searches in `grid` with the same searches on hash collections
+//!
+//! ```text
+//! cargo run --release -p grid --example maze_benchmark
+//! ```
+
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use grid::astar::calculate_path;
+use grid::bfs::bfs_search;
+use grid::{Direction, Grid, Position};
+use utils::random::Rng;
+
+const SIZE: usize = 141;
+const RUNS: u32 = 50;
+
+/// Carves a maze with a randomized depth first search, then opens some extra walls so there are
+/// several routes to the goal
+fn generate_maze(rng: &mut Rng) -> Grid {
+    let mut grid = Grid::new(SIZE, SIZE, '#');
+    let start = Position::new(1, 1);
+    *grid.get_mut(start).unwrap() = '.';
+
+    let mut stack = vec![start];
+    while let Some(&pos) = stack.last() {
+        let unvisited: Vec<Direction> = Direction::all_directions()
+            .into_iter()
+            .filter(|dir| {
+                let next = pos.step(*dir).step(*dir);
+                next.x > 0
+                    && next.y > 0
+                    && next.x < SIZE as i32 - 1
+                    && next.y < SIZE as i32 - 1
+                    && grid.get(next) == Some('#')
+            })
+            .collect();
+
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let dir = *rng.choose(&unvisited);
+        *grid.get_mut(pos.step(dir)).unwrap() = '.';
+        *grid.get_mut(pos.step(dir).step(dir)).unwrap() = '.';
+        stack.push(pos.step(dir).step(dir));
+    }
+
+    for _ in 0..SIZE * 2 {
+        let pos = Position::new(
+            rng.range(1..SIZE as i64 - 1) as i32,
+            rng.range(1..SIZE as i64 - 1) as i32,
+        );
+        *grid.get_mut(pos).unwrap() = '.';
+    }
+
+    grid
+}
+
+fn is_allowed(grid: &Grid, _: Position, pos: Position) -> bool {
+    grid.get(pos) == Some('.')
+}
+
+fn hash_bfs(grid: &Grid, start: Position, goal: Position) -> Option<usize> {
+    let mut visited = HashSet::new();
+    let mut open = VecDeque::from([(start, 0)]);
+
+    while let Some((pos, cost)) = open.pop_front() {
+        if pos == goal {
+            return Some(cost);
+        }
+        if !visited.insert(pos) {
+            continue;
+        }
+        for dir in Direction::all_directions() {
+            let neighbor = pos + dir;
+            if !visited.contains(&neighbor) && is_allowed(grid, pos, neighbor) {
+                open.push_back((neighbor, cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+fn hash_astar(grid: &Grid, start: Position, goal: Position) -> Option<Vec<Position>> {
+    let h = |pos: Position| (goal.x - pos.x).abs() + (goal.y - pos.y).abs();
+
+    let mut open = BinaryHeap::from([(-h(start), 0, start.x, start.y)]);
+    let mut came_from: HashMap<Position, Position> = HashMap::new();
+    let mut g_scores = HashMap::from([(start, 0)]);
+
+    while let Some((_, g_score, x, y)) = open.pop() {
+        let pos = Position::new(x, y);
+        if pos == goal {
+            let mut path = vec![pos];
+            while let Some(previous) = came_from.get(path.last().unwrap()) {
+                path.push(*previous);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if g_scores.get(&pos).is_some_and(|best| g_score > *best) {
+            continue;
+        }
+        for dir in Direction::all_directions() {
+            let neighbor = pos + dir;
+            let tentative = g_score + 1;
+            if !is_allowed(grid, pos, neighbor)
+                || g_scores
+                    .get(&neighbor)
+                    .is_some_and(|best| tentative >= *best)
+            {
+                continue;
+            }
+            g_scores.insert(neighbor, tentative);
+            came_from.insert(neighbor, pos);
+            open.push((
+                -(tentative + h(neighbor)),
+                tentative,
+                neighbor.x,
+                neighbor.y,
+            ));
+        }
+    }
+
+    None
+}
+
+fn time<R: PartialEq + std::fmt::Debug>(mut run: impl FnMut() -> R) -> (R, Duration) {
+    let start = Instant::now();
+    let result = run();
+    for _ in 1..RUNS {
+        assert_eq!(run(), result);
+    }
+    (result, start.elapsed() / RUNS)
+}
+
+fn main() {
+    let grid = generate_maze(&mut Rng::new(141));
+    let start = Position::new(1, 1);
+    let goal = Position::new(SIZE as i32 - 2, SIZE as i32 - 2);
+
+    let (hash, hash_time) = time(|| hash_bfs(&grid, start, goal));
+    let (dense, dense_time) = time(|| bfs_search(&grid, start, goal, is_allowed));
+    assert_eq!(hash, dense);
+    println!("bfs: {hash_time:?} with hash collections, {dense_time:?} with grid collections");
+
+    let (hash, hash_time) = time(|| hash_astar(&grid, start, goal).map(|path| path.len()));
+    let (dense, dense_time) =
+        time(|| calculate_path(&grid, start, goal, is_allowed).map(|path| path.len()));
+    assert_eq!(hash, dense);
+    println!("astar: {hash_time:?} with hash collections, {dense_time:?} with grid collections");
+}
@@ -1,5 +1,6 @@
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::BinaryHeap;
 
+use crate::collections::PositionMap;
 use crate::{Direction, Grid, Position};
 
 #[derive(Eq, PartialEq, Debug)]
@@ -21,18 +22,23 @@ impl Ord for OpenPosition {
     }
 }
 
+/// Returns the shortest path from start to goal, including both. Only positions inside the grid
+/// are visited, whatever `is_allowed` says about the positions around it.
 pub fn calculate_path(
     grid: &Grid,
     start: Position,
     goal: Position,
     is_allowed: impl Fn(&Grid, Position, Position) -> bool,
 ) -> Option<Vec<Position>> {
-    let grid_positions = grid.height() * grid.width();
+    if !grid.in_bounds(start) {
+        return None;
+    }
 
-    let mut open_list = BinaryHeap::with_capacity(grid_positions);
-    let mut came_from = HashMap::with_capacity(grid_positions);
-    let mut closed_list = HashMap::with_capacity(grid_positions);
+    let mut open_list = BinaryHeap::with_capacity(grid.height() * grid.width());
+    let mut came_from = PositionMap::for_grid(grid);
+    let mut g_scores = PositionMap::for_grid(grid);
 
+    g_scores.insert(start, 0);
     open_list.push(OpenPosition {
         pos: start,
         g_score: 0,
@@ -44,23 +50,29 @@ pub fn calculate_path(
             return Some(reconstruct_path(&came_from, current.pos));
         }
 
-        closed_list.insert(current.pos, current.g_score);
+        // A shorter route to this position was found after this entry was pushed
+        if g_scores
+            .get(&current.pos)
+            .is_some_and(|g_score| current.g_score > *g_score)
+        {
+            continue;
+        }
 
         for dir in Direction::all_directions() {
             let neighbor = current.pos + dir;
-            if !is_allowed(grid, current.pos, neighbor)
-                || open_list.iter().any(|x| x.pos == neighbor)
-            {
+            if !grid.in_bounds(neighbor) || !is_allowed(grid, current.pos, neighbor) {
                 continue;
             }
 
             let tentative = current.g_score + 1;
-            if let Some(g_score) = closed_list.get(&neighbor)
-                && tentative >= *g_score
+            if g_scores
+                .get(&neighbor)
+                .is_some_and(|g_score| tentative >= *g_score)
             {
                 continue;
             }
 
+            g_scores.insert(neighbor, tentative);
             came_from.insert(neighbor, current.pos);
             open_list.push(OpenPosition {
                 pos: neighbor,
@@ -77,10 +89,7 @@ fn h(goal: Position, pos: Position) -> i32 {
     distance.x.abs() + distance.y.abs()
 }
 
-fn reconstruct_path(
-    came_from: &HashMap<Position, Position>,
-    mut current: Position,
-) -> Vec<Position> {
+fn reconstruct_path(came_from: &PositionMap<Position>, mut current: Position) -> Vec<Position> {
     let mut path = Vec::new();
     path.push(current);
     while let Some(new) = came_from.get(&current) {
@@ -94,6 +103,8 @@ fn reconstruct_path(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bfs::bfs_search;
+    use utils::random::Rng;
 
     #[test]
     fn simple() {
@@ -153,4 +164,48 @@ S..x..x......
 
         assert_eq!(path.unwrap().len(), 15);
     }
+
+    #[test]
+    fn matches_bfs_on_random_mazes() {
+        let mut rng = Rng::new(45);
+
+        for _ in 0..500 {
+            let width = rng.range(1..25) as usize;
+            let height = rng.range(1..25) as usize;
+            let mut grid = Grid::new(width, height, '.');
+            for (_, row) in grid.iter_rows_mut() {
+                for cell in row.iter_mut().filter(|_| rng.chance(0.35)) {
+                    *cell = 'x';
+                }
+            }
+
+            let mut random_position = || {
+                Position::new(
+                    rng.range(0..width as i64) as i32,
+                    rng.range(0..height as i64) as i32,
+                )
+            };
+            let start = random_position();
+            let goal = random_position();
+            *grid.get_mut(start).unwrap() = '.';
+            *grid.get_mut(goal).unwrap() = '.';
+
+            let is_allowed = |grid: &Grid, _, pos| grid.get(pos) == Some('.');
+            let path = calculate_path(&grid, start, goal, is_allowed);
+            let distance = bfs_search(&grid, start, goal, is_allowed);
+
+            assert_eq!(
+                path.as_ref().map(|path| path.len() - 1),
+                distance,
+                "{grid:?}"
+            );
+            if let Some(path) = path {
+                assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+                assert!(path.windows(2).all(|step| {
+                    let distance = step[0].distance_xy(step[1]);
+                    distance.x.abs() + distance.y.abs() == 1 && is_allowed(&grid, step[0], step[1])
+                }));
+            }
+        }
+    }
 }
@@ -1,14 +1,21 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
+use crate::collections::PositionSet;
 use crate::{Direction, Grid, Position};
 
+/// Returns the amount of steps on the shortest path from start to goal. Only positions inside
+/// the grid are visited, whatever `is_allowed` says about the positions around it.
 pub fn bfs_search(
     grid: &Grid,
     start: Position,
     goal: Position,
     is_allowed: impl Fn(&Grid, Position, Position) -> bool,
 ) -> Option<usize> {
-    let mut visited = HashSet::new();
+    if !grid.in_bounds(start) {
+        return None;
+    }
+
+    let mut visited = PositionSet::for_grid(grid);
     let mut open = VecDeque::new();
 
     open.push_back((start, 0));
@@ -24,7 +31,10 @@ pub fn bfs_search(
 
         for dir in Direction::all_directions() {
             let neighbor = pos + dir;
-            if visited.contains(&neighbor) || !is_allowed(grid, pos, neighbor) {
+            if !grid.in_bounds(neighbor)
+                || visited.contains(&neighbor)
+                || !is_allowed(grid, pos, neighbor)
+            {
                 continue;
             }
 
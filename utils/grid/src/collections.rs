@@ -0,0 +1,305 @@
+use crate::{Grid, Position};
+
+/// Maps the positions inside a `width` x `height` area to indices of a flat array, row by row
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Bounds {
+    width: usize,
+    height: usize,
+}
+
+impl Bounds {
+    fn index(&self, pos: Position) -> Option<usize> {
+        if pos.x < 0 || pos.y < 0 || pos.x as usize >= self.width || pos.y as usize >= self.height {
+            return None;
+        }
+        Some(pos.y as usize * self.width + pos.x as usize)
+    }
+
+    fn checked_index(&self, pos: Position) -> usize {
+        self.index(pos).unwrap_or_else(|| {
+            panic!(
+                "{pos:?} is outside of the {}x{} area",
+                self.width, self.height
+            )
+        })
+    }
+
+    fn position(&self, index: usize) -> Position {
+        Position::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    fn len(&self) -> usize {
+        self.width * self.height
+    }
+}
+
+/// Set of positions inside a fixed area, stored as one bit per position instead of hashing
+///
+/// Has the same methods as `HashSet<Position>`. Positions outside of the area are never
+/// contained and inserting one panics.
+///
+/// # Example
+///
+/// ```
+/// use grid::collections::PositionSet;
+/// use grid::Position;
+///
+/// let mut visited = PositionSet::new(10, 10);
+///
+/// assert!(visited.insert(Position::new(3, 4)));
+/// assert!(!visited.insert(Position::new(3, 4)));
+/// assert!(visited.contains(&Position::new(3, 4)));
+/// assert!(!visited.contains(&Position::new(-1, 4)));
+/// assert_eq!(visited.len(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionSet {
+    bits: Vec<u64>,
+    bounds: Bounds,
+    len: usize,
+}
+
+impl PositionSet {
+    pub fn new(width: usize, height: usize) -> Self {
+        let bounds = Bounds { width, height };
+        Self {
+            bits: vec![0; bounds.len().div_ceil(64)],
+            bounds,
+            len: 0,
+        }
+    }
+
+    /// Creates a set that can hold every position of the grid
+    pub fn for_grid(grid: &Grid) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    /// Returns true when the position was not in the set yet
+    pub fn insert(&mut self, pos: Position) -> bool {
+        let index = self.bounds.checked_index(pos);
+        let (word, mask) = (index / 64, 1 << (index % 64));
+
+        let added = self.bits[word] & mask == 0;
+        self.bits[word] |= mask;
+        self.len += added as usize;
+        added
+    }
+
+    /// Returns true when the position was in the set
+    pub fn remove(&mut self, pos: &Position) -> bool {
+        let Some(index) = self.bounds.index(*pos) else {
+            return false;
+        };
+        let (word, mask) = (index / 64, 1 << (index % 64));
+
+        let removed = self.bits[word] & mask != 0;
+        self.bits[word] &= !mask;
+        self.len -= removed as usize;
+        removed
+    }
+
+    pub fn contains(&self, pos: &Position) -> bool {
+        self.bounds
+            .index(*pos)
+            .is_some_and(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+        self.len = 0;
+    }
+
+    /// Iterates the positions row by row
+    pub fn iter(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.bounds.len())
+            .filter(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+            .map(|index| self.bounds.position(index))
+    }
+}
+
+impl Extend<Position> for PositionSet {
+    fn extend<I: IntoIterator<Item = Position>>(&mut self, iter: I) {
+        for pos in iter {
+            self.insert(pos);
+        }
+    }
+}
+
+/// Map from the positions inside a fixed area to values, stored in a flat `Vec` instead of
+/// hashing
+///
+/// Has the same methods as `HashMap<Position, T>`. Positions outside of the area are never
+/// contained and inserting one panics.
+///
+/// # Example
+///
+/// ```
+/// use grid::collections::PositionMap;
+/// use grid::Position;
+///
+/// let mut costs = PositionMap::new(5, 5);
+///
+/// assert_eq!(costs.insert(Position::new(1, 2), 7), None);
+/// assert_eq!(costs.insert(Position::new(1, 2), 3), Some(7));
+/// assert_eq!(costs.get(&Position::new(1, 2)), Some(&3));
+/// assert_eq!(costs.get(&Position::new(9, 2)), None);
+/// assert_eq!(costs.len(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionMap<T> {
+    values: Vec<Option<T>>,
+    bounds: Bounds,
+    len: usize,
+}
+
+impl<T> PositionMap<T> {
+    pub fn new(width: usize, height: usize) -> Self {
+        let bounds = Bounds { width, height };
+        Self {
+            values: (0..bounds.len()).map(|_| None).collect(),
+            bounds,
+            len: 0,
+        }
+    }
+
+    /// Creates a map that can hold every position of the grid
+    pub fn for_grid(grid: &Grid) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    /// Returns the previous value of the position
+    pub fn insert(&mut self, pos: Position, value: T) -> Option<T> {
+        let index = self.bounds.checked_index(pos);
+
+        let previous = self.values[index].replace(value);
+        self.len += previous.is_none() as usize;
+        previous
+    }
+
+    pub fn remove(&mut self, pos: &Position) -> Option<T> {
+        let index = self.bounds.index(*pos)?;
+
+        let previous = self.values[index].take();
+        self.len -= previous.is_some() as usize;
+        previous
+    }
+
+    pub fn get(&self, pos: &Position) -> Option<&T> {
+        self.values[self.bounds.index(*pos)?].as_ref()
+    }
+
+    pub fn get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        self.values[self.bounds.index(*pos)?].as_mut()
+    }
+
+    pub fn contains_key(&self, pos: &Position) -> bool {
+        self.get(pos).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.values.fill_with(|| None);
+        self.len = 0;
+    }
+
+    /// Iterates the entries row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| Some((self.bounds.position(index), value.as_ref()?)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Position> + '_ {
+        self.iter().map(|(pos, _)| pos)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<T> Extend<(Position, T)> for PositionMap<T> {
+    fn extend<I: IntoIterator<Item = (Position, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+    use utils::random::Rng;
+
+    #[test]
+    fn behaves_like_hash_collections() {
+        let mut rng = Rng::new(48);
+        let (width, height) = (13, 7);
+
+        let mut set = PositionSet::new(width, height);
+        let mut map = PositionMap::new(width, height);
+        let mut expected_set = HashSet::new();
+        let mut expected_map = HashMap::new();
+
+        for step in 0..2000 {
+            // Also tries positions just outside of the area
+            let pos = Position::new(
+                rng.range(-1..width as i64 + 1) as i32,
+                rng.range(-1..height as i64 + 1) as i32,
+            );
+            let in_bounds =
+                pos.x >= 0 && pos.y >= 0 && pos.x < width as i32 && pos.y < height as i32;
+
+            if in_bounds && rng.chance(0.6) {
+                assert_eq!(set.insert(pos), expected_set.insert(pos));
+                assert_eq!(map.insert(pos, step), expected_map.insert(pos, step));
+            } else {
+                assert_eq!(set.remove(&pos), expected_set.remove(&pos));
+                assert_eq!(map.remove(&pos), expected_map.remove(&pos));
+            }
+
+            assert_eq!(set.contains(&pos), expected_set.contains(&pos));
+            assert_eq!(map.get(&pos), expected_map.get(&pos));
+            assert_eq!(
+                (set.len(), map.len()),
+                (expected_set.len(), expected_map.len())
+            );
+        }
+
+        assert_eq!(set.iter().collect::<HashSet<_>>(), expected_set);
+        assert_eq!(
+            map.iter()
+                .map(|(pos, value)| (pos, *value))
+                .collect::<HashMap<_, _>>(),
+            expected_map
+        );
+
+        set.clear();
+        map.clear();
+        assert!(set.is_empty() && map.is_empty());
+        assert_eq!(set.iter().count() + map.iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the 3x3 area")]
+    fn insert_outside() {
+        PositionSet::new(3, 3).insert(Position::new(3, 0));
+    }
+}
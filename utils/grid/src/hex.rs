@@ -0,0 +1,213 @@
+use std::ops::{Add, AddAssign, Sub};
+use std::str::FromStr;
+
+/// The six neighbors on a hex grid with flat topped cells, so north and south are straight up and
+/// down while the other four are diagonal
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// All directions, clockwise starting at north
+    pub fn all_directions() -> [Self; 6] {
+        [
+            HexDirection::North,
+            HexDirection::NorthEast,
+            HexDirection::SouthEast,
+            HexDirection::South,
+            HexDirection::SouthWest,
+            HexDirection::NorthWest,
+        ]
+    }
+
+    /// Turns 60 degrees clockwise
+    pub fn turn_right(&self) -> Self {
+        Self::all_directions()[(*self as usize + 1) % 6]
+    }
+
+    /// Turns 60 degrees counterclockwise
+    pub fn turn_left(&self) -> Self {
+        Self::all_directions()[(*self as usize + 5) % 6]
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::all_directions()[(*self as usize + 3) % 6]
+    }
+
+    /// Returns the offset to the neighbor in this direction
+    pub fn delta(&self) -> Hex {
+        match self {
+            HexDirection::North => Hex::new(0, -1),
+            HexDirection::NorthEast => Hex::new(1, -1),
+            HexDirection::SouthEast => Hex::new(1, 0),
+            HexDirection::South => Hex::new(0, 1),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::NorthWest => Hex::new(-1, 0),
+        }
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = String;
+
+    /// Parses `n`, `ne`, `se`, `s`, `sw` and `nw`, in any case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "n" => Ok(HexDirection::North),
+            "ne" => Ok(HexDirection::NorthEast),
+            "se" => Ok(HexDirection::SouthEast),
+            "s" => Ok(HexDirection::South),
+            "sw" => Ok(HexDirection::SouthWest),
+            "nw" => Ok(HexDirection::NorthWest),
+            _ => Err(format!("Unknown hex direction {s}")),
+        }
+    }
+}
+
+/// A cell on a hex grid in axial coordinates, the third cube coordinate is `s = -q - r`
+///
+/// # Example
+///
+/// ```
+/// use grid::hex::{Hex, HexDirection};
+///
+/// let path: Vec<HexDirection> = "se,sw,se,sw,sw".split(',').map(|dir| dir.parse().unwrap()).collect();
+/// let end = path.iter().fold(Hex::default(), |pos, dir| pos + *dir);
+///
+/// assert_eq!(end.distance(Hex::default()), 3);
+/// assert_eq!(end.q + end.r + end.s(), 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    /// Creates the cell from cube coordinates, which have to sum up to zero
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates should sum up to zero");
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn step(&self, direction: HexDirection) -> Self {
+        *self + direction.delta()
+    }
+
+    /// Returns the neighbors in the order of `HexDirection::all_directions`
+    pub fn neighbors(&self) -> [Self; 6] {
+        HexDirection::all_directions().map(|direction| self.step(direction))
+    }
+
+    /// Returns the amount of steps between the cells
+    pub fn distance(&self, other: Self) -> i32 {
+        let diff = *self - other;
+        (diff.q.abs() + diff.r.abs() + diff.s().abs()) / 2
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            q: self.q + rhs.q,
+            r: self.r + rhs.r,
+        }
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            q: self.q - rhs.q,
+            r: self.r - rhs.r,
+        }
+    }
+}
+
+impl Add<HexDirection> for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: HexDirection) -> Self::Output {
+        self.step(rhs)
+    }
+}
+
+impl AddAssign<HexDirection> for Hex {
+    fn add_assign(&mut self, rhs: HexDirection) {
+        *self = self.step(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::random::Rng;
+
+    fn walk(path: &str) -> Hex {
+        path.split(',')
+            .map(|dir| dir.parse::<HexDirection>().unwrap())
+            .fold(Hex::default(), |pos, dir| pos + dir)
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(walk("ne,ne,ne").distance(Hex::default()), 3);
+        assert_eq!(walk("ne,ne,sw,sw").distance(Hex::default()), 0);
+        assert_eq!(walk("ne,ne,s,s").distance(Hex::default()), 2);
+        assert_eq!(walk("N,Se,s,SW,nw,ne"), Hex::default());
+        assert!("e".parse::<HexDirection>().is_err());
+    }
+
+    #[test]
+    fn random_walks() {
+        let mut rng = Rng::new(49);
+
+        for _ in 0..200 {
+            let start = Hex::new(rng.range(-50..50) as i32, rng.range(-50..50) as i32);
+            let mut pos = start;
+            let mut steps = 0;
+
+            for _ in 0..rng.range(0..30) {
+                let dir = *rng.choose(&HexDirection::all_directions());
+                assert_eq!(dir.turn_right().turn_left(), dir);
+                assert_eq!(dir.turn_right().turn_right().turn_right(), dir.reverse());
+                assert!(pos.neighbors().iter().all(|next| next.distance(pos) == 1));
+
+                pos += dir;
+                steps += 1;
+                assert!(pos.distance(start) <= steps);
+            }
+
+            // Greedily stepping to the closest neighbor gets back in exactly the distance
+            let distance = pos.distance(start);
+            let mut back = 0;
+            while pos != start {
+                pos = *pos
+                    .neighbors()
+                    .iter()
+                    .min_by_key(|next| next.distance(start))
+                    .unwrap();
+                back += 1;
+            }
+            assert_eq!(back, distance);
+        }
+    }
+}
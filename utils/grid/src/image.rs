@@ -0,0 +1,296 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::{Grid, Position};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+    pub const GREEN: Rgb = Rgb(0, 255, 0);
+    pub const BLUE: Rgb = Rgb(0, 0, 255);
+
+    /// Returns the perceived brightness, used for grayscale output
+    pub fn luma(&self) -> u8 {
+        ((self.0 as u32 * 299 + self.1 as u32 * 587 + self.2 as u32 * 114) / 1000) as u8
+    }
+}
+
+/// A picture of a grid where every cell is drawn as a `scale` by `scale` block of pixels
+///
+/// # Example
+///
+/// ```
+/// use grid::{Grid, Position, image::{Image, Rgb}};
+///
+/// let grid = Grid::from_text("#.\n.#");
+/// let mut image = Image::from_grid(&grid, 2, |ch| if ch == '#' { Rgb::BLACK } else { Rgb::WHITE });
+/// image.overlay(&[Position::new(1, 0)], Rgb::RED);
+///
+/// assert_eq!((image.width(), image.height()), (4, 4));
+/// assert_eq!(image.pixel(0, 0), Rgb::BLACK);
+/// assert_eq!(image.pixel(3, 1), Rgb::RED);
+///
+/// let mut ppm = Vec::new();
+/// image.write_ppm(&mut ppm).unwrap();
+/// assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pixels: Vec<Rgb>,
+    width: usize,
+    height: usize,
+    scale: usize,
+}
+
+impl Image {
+    pub fn from_grid(grid: &Grid, scale: usize, palette: impl Fn(char) -> Rgb) -> Self {
+        assert!(scale > 0, "Scale should be at least 1");
+
+        let width = grid.width() * scale;
+        let height = grid.height() * scale;
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for (_, row) in grid.iter_rows() {
+            let line: Vec<Rgb> = row
+                .iter()
+                .flat_map(|ch| std::iter::repeat_n(palette(*ch), scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+
+        Self {
+            pixels,
+            width,
+            height,
+            scale,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Returns all pixels row by row
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// Colours the cells at the positions, positions outside the grid are ignored
+    pub fn overlay(&mut self, positions: &[Position], colour: Rgb) {
+        let columns = (self.width / self.scale) as i32;
+        let rows = (self.height / self.scale) as i32;
+
+        for pos in positions
+            .iter()
+            .filter(|pos| pos.x >= 0 && pos.x < columns && pos.y >= 0 && pos.y < rows)
+        {
+            for y in 0..self.scale {
+                let start =
+                    (pos.y as usize * self.scale + y) * self.width + pos.x as usize * self.scale;
+                self.pixels[start..start + self.scale].fill(colour);
+            }
+        }
+    }
+
+    pub fn write_ppm(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        let bytes: Vec<u8> = self.pixels.iter().flat_map(|p| [p.0, p.1, p.2]).collect();
+        writer.write_all(&bytes)
+    }
+
+    pub fn write_pgm(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(writer, "P5\n{} {}\n255\n", self.width, self.height)?;
+        let bytes: Vec<u8> = self.pixels.iter().map(|p| p.luma()).collect();
+        writer.write_all(&bytes)
+    }
+
+    /// Writes an 8 bit RGB png, the image data is stored without compression. Fails for an
+    /// image without pixels because png has no way to store one.
+    pub fn write_png(&self, writer: &mut impl Write) -> io::Result<()> {
+        if self.width == 0 || self.height == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "A png image needs at least one pixel",
+            ));
+        }
+
+        writer.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // Bit depth 8, colour type RGB, default compression, filtering and no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        write_png_chunk(writer, b"IHDR", &header)?;
+
+        let mut raw = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width) {
+            raw.push(0); // No filter
+            raw.extend(row.iter().flat_map(|p| [p.0, p.1, p.2]));
+        }
+        write_png_chunk(writer, b"IDAT", &zlib_stored(&raw))?;
+
+        write_png_chunk(writer, b"IEND", &[])
+    }
+
+    /// Saves the image, the format is picked from the extension: `ppm`, `pgm` or `png`. The file
+    /// is only created once the extension and size are known to be fine.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+
+        type WriteFn = fn(&Image, &mut BufWriter<File>) -> io::Result<()>;
+        let write: WriteFn = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => Self::write_ppm,
+            Some("pgm") => Self::write_pgm,
+            Some("png") => Self::write_png,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Image extension should be ppm, pgm or png",
+                ));
+            }
+        };
+        if self.pixels.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Image should have at least one pixel",
+            ));
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        write(self, &mut writer)?;
+        writer.flush()
+    }
+}
+
+fn write_png_chunk(writer: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+
+    let crc = crc32(kind.iter().chain(data));
+    writer.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps the data in a zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+
+    let mut output = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    output.extend_from_slice(&[0x78, 0x01]);
+
+    let blocks: Vec<&[u8]> = if data.is_empty() {
+        vec![&[]]
+    } else {
+        data.chunks(MAX_BLOCK).collect()
+    };
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i + 1 == blocks.len();
+        let len = block.len() as u16;
+        output.push(last as u8);
+        output.extend_from_slice(&len.to_le_bytes());
+        output.extend_from_slice(&(!len).to_le_bytes());
+        output.extend_from_slice(block);
+    }
+
+    output.extend_from_slice(&adler32(data).to_be_bytes());
+    output
+}
+
+fn crc32<'a>(data: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND".iter()), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn png_layout() {
+        let grid = Grid::new(3, 2, '.');
+        let image = Image::from_grid(&grid, 1, |_| Rgb(1, 2, 3));
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        // Two rows of a filter byte and three pixels stored in a single deflate block
+        let idat_length = u32::from_be_bytes(png[33..37].try_into().unwrap());
+        assert_eq!(&png[37..41], b"IDAT");
+        assert_eq!(idat_length, 2 + 5 + 2 * (1 + 3 * 3) + 4);
+    }
+
+    #[test]
+    fn empty_png() {
+        let image = Image::from_grid(&Grid::default(), 2, |_| Rgb::BLACK);
+
+        let mut png = Vec::new();
+        let error = image.write_png(&mut png).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(png.is_empty());
+    }
+
+    #[test]
+    fn failed_save_leaves_no_file() {
+        let dir = std::env::temp_dir().join(format!("grid-image-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let existing = dir.join("existing.jpg");
+        std::fs::write(&existing, b"keep me").unwrap();
+        let image = Image::from_grid(&Grid::new(2, 2, '.'), 1, |_| Rgb::WHITE);
+        assert!(image.save(&existing).is_err());
+        assert_eq!(std::fs::read(&existing).unwrap(), b"keep me");
+
+        let empty = Image::from_grid(&Grid::default(), 1, |_| Rgb::WHITE);
+        let missing = dir.join("empty.png");
+        assert!(empty.save(&missing).is_err());
+        assert!(!missing.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,10 +1,28 @@
 pub mod astar;
 pub mod bfs;
+pub mod collections;
+pub mod hex;
+pub mod image;
+pub mod polygon;
+pub mod recorder;
+pub mod space;
+pub mod terminal;
 
 use core::panic;
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// Which way the y coordinate grows when moving up or down
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum YAxis {
+    /// Rows are counted from the top, like the lines of a puzzle input
+    #[default]
+    Down,
+    /// Rows are counted from the bottom, like a graph in math
+    Up,
+}
+
+/// The four straight directions, `dir as usize` gives the index in `all_directions`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
     Up,
     Down,
@@ -13,13 +31,38 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn from_char(ch: char) -> Self {
+    /// Parses an arrow (`^v<>`), a letter (`UDLR` or `NSEW`, in any case) or a digit (`0` to
+    /// `3` for right, down, left and up)
+    pub fn parse(ch: char) -> Option<Self> {
         match ch {
-            '>' => Direction::Right,
-            '<' => Direction::Left,
-            '^' => Direction::Up,
-            'v' => Direction::Down,
-            _ => panic!("Parsing failed"),
+            '^' | 'U' | 'u' | 'N' | 'n' | '3' => Some(Direction::Up),
+            'v' | 'D' | 'd' | 'S' | 's' | '1' => Some(Direction::Down),
+            '<' | 'L' | 'l' | 'W' | 'w' | '2' => Some(Direction::Left),
+            '>' | 'R' | 'r' | 'E' | 'e' | '0' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    /// Like `parse` but panics when the character is not a direction
+    pub fn from_char(ch: char) -> Self {
+        Self::parse(ch).unwrap_or_else(|| panic!("Can't parse {ch:?} as a direction"))
+    }
+
+    /// Returns the step in this direction with y growing downwards
+    pub fn distance(&self) -> Distance {
+        self.distance_in(YAxis::Down)
+    }
+
+    pub fn distance_in(&self, y_axis: YAxis) -> Distance {
+        let up = match y_axis {
+            YAxis::Down => -1,
+            YAxis::Up => 1,
+        };
+        match self {
+            Direction::Up => Distance::new(0, up),
+            Direction::Down => Distance::new(0, -up),
+            Direction::Left => Distance::new(-1, 0),
+            Direction::Right => Distance::new(1, 0),
         }
     }
 
@@ -60,6 +103,83 @@ impl Direction {
     }
 }
 
+/// The four straight and four diagonal directions, for puzzles where diagonal moves count
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting at up
+    pub fn all_directions() -> [Self; 8] {
+        [
+            Direction8::Up,
+            Direction8::UpRight,
+            Direction8::Right,
+            Direction8::DownRight,
+            Direction8::Down,
+            Direction8::DownLeft,
+            Direction8::Left,
+            Direction8::UpLeft,
+        ]
+    }
+
+    pub fn turn_right_45(&self) -> Self {
+        Self::all_directions()[(*self as usize + 1) % 8]
+    }
+
+    pub fn turn_left_45(&self) -> Self {
+        Self::all_directions()[(*self as usize + 7) % 8]
+    }
+
+    pub fn turn_right(&self) -> Self {
+        Self::all_directions()[(*self as usize + 2) % 8]
+    }
+
+    pub fn turn_left(&self) -> Self {
+        Self::all_directions()[(*self as usize + 6) % 8]
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::all_directions()[(*self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+
+    pub fn distance(&self) -> Distance {
+        match self {
+            Direction8::Up => Distance::new(0, -1),
+            Direction8::UpRight => Distance::new(1, -1),
+            Direction8::Right => Distance::new(1, 0),
+            Direction8::DownRight => Distance::new(1, 1),
+            Direction8::Down => Distance::new(0, 1),
+            Direction8::DownLeft => Distance::new(-1, 1),
+            Direction8::Left => Distance::new(-1, 0),
+            Direction8::UpLeft => Distance::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct Position {
     pub x: i32,
@@ -72,24 +192,12 @@ impl Position {
     }
 
     pub fn step(&self, direction: Direction) -> Self {
-        match direction {
-            Direction::Up => Self {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::Down => Self {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::Left => Self {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Direction::Right => Self {
-                x: self.x + 1,
-                y: self.y,
-            },
-        }
+        *self + direction.distance()
+    }
+
+    /// Steps in the direction for grids where y does not grow downwards
+    pub fn step_in(&self, direction: Direction, y_axis: YAxis) -> Self {
+        *self + direction.distance_in(y_axis)
     }
 
     pub fn distance_xy(&self, other: Self) -> Distance {
@@ -126,6 +234,33 @@ impl Distance {
     }
 }
 
+impl From<Direction> for Distance {
+    fn from(direction: Direction) -> Self {
+        direction.distance()
+    }
+}
+
+impl TryFrom<Distance> for Direction {
+    type Error = Distance;
+
+    /// Only succeeds for a single step up, down, left or right, returns the distance otherwise
+    fn try_from(distance: Distance) -> Result<Self, Self::Error> {
+        Direction::all_directions()
+            .into_iter()
+            .find(|direction| direction.distance() == distance)
+            .ok_or(distance)
+    }
+}
+
+impl Mul<i32> for Direction {
+    type Output = Distance;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        let distance = self.distance();
+        Distance::new(distance.x * rhs, distance.y * rhs)
+    }
+}
+
 impl Add<Direction> for Position {
     type Output = Self;
 
@@ -134,6 +269,14 @@ impl Add<Direction> for Position {
     }
 }
 
+impl Add<Direction8> for Position {
+    type Output = Self;
+
+    fn add(self, rhs: Direction8) -> Self::Output {
+        self + rhs.distance()
+    }
+}
+
 impl Add<Distance> for Position {
     type Output = Self;
 
@@ -227,6 +370,30 @@ impl Grid {
         }
     }
 
+    /// Like `from_text` but allows rows of different lengths, short rows are padded on the right
+    pub fn from_text_padded(input: &str, fill: char) -> Self {
+        let width = input
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut grid = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for line in input.lines() {
+            let start = grid.len();
+            grid.extend(line.chars());
+            grid.resize(start + width, fill);
+            height += 1;
+        }
+
+        Grid {
+            grid,
+            width,
+            height,
+        }
+    }
+
     pub fn in_bounds(&self, pos: Position) -> bool {
         pos.x >= 0 && pos.x < self.width as i32 && pos.y >= 0 && pos.y < self.height as i32
     }
@@ -273,11 +440,12 @@ impl Grid {
     }
 
     pub fn iter_rows(&self) -> impl Iterator<Item = (usize, &[char])> + '_ {
-        self.grid.chunks(self.width).enumerate()
+        // An empty grid has no cells, the chunk size only has to be valid
+        self.grid.chunks(self.width.max(1)).enumerate()
     }
 
     pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = (usize, &mut [char])> + '_ {
-        self.grid.chunks_mut(self.width).enumerate()
+        self.grid.chunks_mut(self.width.max(1)).enumerate()
     }
 
     pub fn find_one(&self, item: char) -> Option<Position> {
@@ -302,7 +470,7 @@ impl Grid {
 
     pub fn print(&self) {
         self.grid
-            .chunks(self.width)
+            .chunks(self.width.max(1))
             .for_each(|line| println!("{}", line.iter().collect::<String>()))
     }
 
@@ -332,6 +500,7 @@ impl Grid {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::random::Rng;
 
     #[test]
     fn transpose() {
@@ -352,4 +521,98 @@ mod tests {
         let grid2 = Grid::from_text(grid2.trim());
         assert_eq!(grid1.transpose(), grid2);
     }
+
+    #[test]
+    fn direction_conversions() {
+        assert_eq!(
+            "^v<>".chars().map(Direction::from_char).collect::<Vec<_>>(),
+            "UDLR".chars().map(Direction::from_char).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "nsWE3120".chars().map(Direction::parse).collect::<Vec<_>>(),
+            "udlruDLR".chars().map(Direction::parse).collect::<Vec<_>>()
+        );
+        assert_eq!(Direction::parse('x'), None);
+
+        for (i, dir) in Direction::all_directions().into_iter().enumerate() {
+            assert_eq!(dir as usize, i);
+            assert_eq!(Direction::try_from(Distance::from(dir)), Ok(dir));
+            assert_eq!(
+                Position::new(2, 5) + dir * 3,
+                Position::new(2, 5) + dir + dir + dir
+            );
+            assert_eq!(dir.distance_in(YAxis::Up).x, dir.distance().x);
+            assert_eq!(dir.distance_in(YAxis::Up).y, -dir.distance().y);
+        }
+        assert_eq!(Direction::Up * -2, Direction::Down * 2);
+        assert_eq!(
+            Direction::try_from(Distance::new(1, 1)),
+            Err(Distance::new(1, 1))
+        );
+        assert_eq!(
+            Position::new(0, 0).step_in(Direction::Up, YAxis::Up),
+            Position::new(0, 1)
+        );
+    }
+
+    #[test]
+    fn direction8_turns() {
+        for dir in Direction8::all_directions() {
+            assert_eq!(dir.turn_right_45().turn_right_45(), dir.turn_right());
+            assert_eq!(dir.turn_left_45().turn_right_45(), dir);
+            assert_eq!(dir.turn_left().turn_left(), dir.reverse());
+            assert_eq!(
+                Position::new(0, 0) + dir + dir.reverse(),
+                Position::new(0, 0)
+            );
+            assert_ne!(dir.is_diagonal(), dir.turn_right_45().is_diagonal());
+        }
+
+        for dir in Direction::all_directions() {
+            assert_eq!(
+                Position::new(3, 3) + Direction8::from(dir),
+                Position::new(3, 3) + dir
+            );
+            assert_eq!(
+                Direction8::from(dir.turn_right()),
+                Direction8::from(dir).turn_right()
+            );
+        }
+
+        let mut distances: Vec<_> = Direction8::all_directions()
+            .map(|dir| dir.distance())
+            .to_vec();
+        distances.sort_by_key(|distance| (distance.y, distance.x));
+        assert_eq!(distances, Distance::eight_directions());
+    }
+
+    #[test]
+    fn padded() {
+        let grid = Grid::from_text_padded("12\n3\n\n456", ' ');
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid, Grid::from_text("12 \n3  \n   \n456"));
+    }
+
+    #[test]
+    fn transpose_twice_is_identity() {
+        let mut rng = Rng::new(7);
+        for _ in 0..200 {
+            let width = rng.range(1..20) as usize;
+            let height = rng.range(1..20) as usize;
+            let mut grid = Grid::new(width, height, '.');
+            for (_, row) in grid.iter_rows_mut() {
+                for cell in row.iter_mut() {
+                    *cell = *rng.choose(&['.', '#', 'O', 'x']);
+                }
+            }
+
+            let transposed = grid.transpose();
+            assert_eq!((transposed.width(), transposed.height()), (height, width));
+            assert!(
+                grid.iter()
+                    .all(|(pos, ch)| transposed.get(Position::new(pos.y, pos.x)) == Some(ch))
+            );
+            assert_eq!(transposed.transpose(), grid);
+        }
+    }
 }
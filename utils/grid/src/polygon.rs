@@ -0,0 +1,240 @@
+use utils::greatest_common_divisor;
+
+use crate::{Direction, Position};
+
+/// A closed polygon on the integer lattice, stored with 64 bit coordinates so large
+/// (for example hex encoded) step lengths don't overflow
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<(i64, i64)>,
+}
+
+impl Polygon {
+    /// Creates a polygon from its vertices, the last vertex connects back to the first one.
+    /// Repeated vertices and vertices in the middle of a straight edge are dropped, so they
+    /// don't count the same boundary points twice.
+    pub fn from_positions(positions: &[Position]) -> Self {
+        Self::from_vertices(positions.iter().map(|pos| (pos.x as i64, pos.y as i64)))
+    }
+
+    /// Creates a polygon by walking the steps starting from the origin
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use grid::{Direction, polygon::Polygon};
+    ///
+    /// let square = Polygon::from_steps([
+    ///     (Direction::Right, 2),
+    ///     (Direction::Down, 2),
+    ///     (Direction::Left, 2),
+    ///     (Direction::Up, 2),
+    /// ]);
+    /// assert_eq!(square.area(), 4);
+    /// assert_eq!(square.boundary_length(), 8);
+    /// assert_eq!(square.interior_points(), 1);
+    /// assert_eq!(square.enclosed_points(), 9);
+    /// ```
+    pub fn from_steps(steps: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        let mut vertices = Vec::new();
+        let mut current = (0, 0);
+
+        for (direction, length) in steps {
+            vertices.push(current);
+            current = match direction {
+                Direction::Up => (current.0, current.1 - length),
+                Direction::Down => (current.0, current.1 + length),
+                Direction::Left => (current.0 - length, current.1),
+                Direction::Right => (current.0 + length, current.1),
+            };
+        }
+
+        Self::from_vertices(vertices)
+    }
+
+    /// Keeps only the corners, the closing edge from the last to the first vertex included
+    fn from_vertices(points: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        let mut vertices: Vec<(i64, i64)> = Vec::new();
+
+        for point in points {
+            if vertices.last() == Some(&point) {
+                continue;
+            }
+            while let [.., a, b] = vertices[..]
+                && is_straight(a, b, point)
+            {
+                vertices.pop();
+            }
+            vertices.push(point);
+        }
+
+        loop {
+            let n = vertices.len();
+            if n >= 2 && vertices[n - 1] == vertices[0]
+                || n >= 3 && is_straight(vertices[n - 2], vertices[n - 1], vertices[0])
+            {
+                vertices.pop();
+            } else if n >= 3 && is_straight(vertices[n - 1], vertices[0], vertices[1]) {
+                vertices.remove(0);
+            } else {
+                return Self { vertices };
+            }
+        }
+    }
+
+    pub fn vertices(&self) -> &[(i64, i64)] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(a, b)| (*a, *b))
+    }
+
+    /// Returns twice the signed area using the shoelace formula, positive when the
+    /// vertices are ordered counter clockwise in a y-up coordinate system
+    pub fn signed_double_area(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| a.0 as i128 * b.1 as i128 - b.0 as i128 * a.1 as i128)
+            .sum()
+    }
+
+    /// Returns the area rounded down, lattice polygons always have an area that is a multiple of a half
+    pub fn area(&self) -> i128 {
+        self.signed_double_area().abs() / 2
+    }
+
+    /// Returns the amount of lattice points on the edges of the polygon
+    pub fn boundary_length(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| {
+                let dx = (b.0 - a.0).unsigned_abs();
+                let dy = (b.1 - a.1).unsigned_abs();
+                greatest_common_divisor(dx, dy) as i128
+            })
+            .sum()
+    }
+
+    /// Returns the amount of lattice points strictly inside the polygon using Pick's theorem
+    pub fn interior_points(&self) -> i128 {
+        let double_area = self.signed_double_area().abs();
+        // Pick's theorem only holds for real polygons, a line or a point has no inside
+        if self.vertices.len() < 3 || double_area == 0 {
+            return 0;
+        }
+        (double_area - self.boundary_length() + 2) / 2
+    }
+
+    /// Returns the amount of lattice points inside or on the edges of the polygon
+    pub fn enclosed_points(&self) -> i128 {
+        self.interior_points() + self.boundary_length()
+    }
+}
+
+/// Returns true when `b` lies on the edge from `a` to `c`, so the direction doesn't change there
+fn is_straight(a: (i64, i64), b: (i64, i64), c: (i64, i64)) -> bool {
+    let (dx1, dy1) = ((b.0 - a.0) as i128, (b.1 - a.1) as i128);
+    let (dx2, dy2) = ((c.0 - b.0) as i128, (c.1 - b.1) as i128);
+    dx1 * dy2 == dy1 * dx2 && dx1 * dx2 + dy1 * dy2 > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangle() {
+        let polygon = Polygon::from_positions(&[
+            Position::new(0, 0),
+            Position::new(4, 0),
+            Position::new(0, 4),
+        ]);
+
+        assert_eq!(polygon.signed_double_area(), 16);
+        assert_eq!(polygon.area(), 8);
+        assert_eq!(polygon.boundary_length(), 12);
+        assert_eq!(polygon.interior_points(), 3);
+    }
+
+    #[test]
+    fn orientation() {
+        let clockwise = Polygon::from_positions(&[
+            Position::new(0, 0),
+            Position::new(0, 3),
+            Position::new(3, 3),
+            Position::new(3, 0),
+        ]);
+        let counter_clockwise = Polygon::from_positions(&[
+            Position::new(0, 0),
+            Position::new(3, 0),
+            Position::new(3, 3),
+            Position::new(0, 3),
+        ]);
+
+        assert_eq!(clockwise.signed_double_area(), -18);
+        assert_eq!(counter_clockwise.signed_double_area(), 18);
+        assert_eq!(clockwise.interior_points(), 4);
+    }
+
+    #[test]
+    fn redundant_vertices() {
+        let square = Polygon::from_positions(&[
+            Position::new(0, 0),
+            Position::new(2, 0),
+            Position::new(4, 0),
+            Position::new(4, 4),
+            Position::new(4, 4),
+            Position::new(0, 4),
+            Position::new(0, 0),
+        ]);
+
+        assert_eq!(square.vertices(), [(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(square.boundary_length(), 16);
+        assert_eq!(square.enclosed_points(), 25);
+
+        // The start is in the middle of the closing edge
+        let steps = Polygon::from_steps([
+            (Direction::Right, 2),
+            (Direction::Down, 3),
+            (Direction::Down, 0),
+            (Direction::Left, 4),
+            (Direction::Up, 3),
+            (Direction::Right, 2),
+        ]);
+        assert_eq!(steps.vertices(), [(2, 0), (2, 3), (-2, 3), (-2, 0)]);
+        assert_eq!(steps.boundary_length(), 14);
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(Polygon::default().interior_points(), 0);
+        assert_eq!(
+            Polygon::from_positions(&[Position::new(2, 3)]).interior_points(),
+            0
+        );
+
+        let line = Polygon::from_positions(&[
+            Position::new(0, 0),
+            Position::new(4, 0),
+            Position::new(6, 0),
+        ]);
+        assert_eq!(line.area(), 0);
+        assert_eq!(line.interior_points(), 0);
+    }
+
+    #[test]
+    fn large_steps() {
+        let polygon = Polygon::from_steps([
+            (Direction::Right, 0xfffff * 1000),
+            (Direction::Down, 0xfffff * 1000),
+            (Direction::Left, 0xfffff * 1000),
+            (Direction::Up, 0xfffff * 1000),
+        ]);
+
+        let side = 0xfffff_i128 * 1000;
+        assert_eq!(polygon.area(), side * side);
+        assert_eq!(polygon.enclosed_points(), (side + 1) * (side + 1));
+    }
+}
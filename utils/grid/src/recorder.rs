@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::Grid;
+use crate::image::{Image, Rgb};
+
+/// Environment variable that enables recording, set it to `frames` for numbered png files or to
+/// anything else for an animated gif
+pub const RECORD_VAR: &str = "AOC_RECORD";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    Gif,
+    Frames,
+}
+
+/// Captures the states of a simulation so they can be watched afterwards
+///
+/// A disabled recorder ignores every capture, so it can stay in the solver without slowing down
+/// normal runs.
+///
+/// # Example
+///
+/// ```
+/// use grid::{Grid, Position, image::Rgb, recorder::{Output, Recorder}};
+///
+/// let mut grid = Grid::new(3, 1, '.');
+/// let mut recorder = Recorder::new(Output::Gif).skip_unchanged(true);
+///
+/// recorder.capture(&grid);
+/// recorder.capture(&grid);
+/// *grid.get_mut(Position::new(1, 0)).unwrap() = '#';
+/// recorder.capture(&grid);
+/// assert_eq!(recorder.len(), 2);
+///
+/// let mut gif = Vec::new();
+/// let palette = |ch| if ch == '#' { Rgb::BLACK } else { Rgb::WHITE };
+/// recorder.write_gif(&mut gif, 4, palette, 10).unwrap();
+/// assert!(gif.starts_with(b"GIF89a"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Recorder {
+    frames: Vec<Grid>,
+    output: Option<Output>,
+    skip_unchanged: bool,
+}
+
+impl Recorder {
+    pub fn new(output: Output) -> Self {
+        Self {
+            output: Some(output),
+            ..Default::default()
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Creates a recorder that is only enabled when `AOC_RECORD` is set
+    pub fn from_env() -> Self {
+        match std::env::var(RECORD_VAR) {
+            Ok(value) if value == "frames" => Self::new(Output::Frames),
+            Ok(value) if !value.is_empty() => Self::new(Output::Gif),
+            _ => Self::disabled(),
+        }
+    }
+
+    /// Skips frames that are equal to the previous captured frame
+    pub fn skip_unchanged(mut self, skip: bool) -> Self {
+        self.skip_unchanged = skip;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.output.is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn capture(&mut self, grid: &Grid) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.skip_unchanged && self.frames.last() == Some(grid) {
+            return;
+        }
+        self.frames.push(grid.clone());
+    }
+
+    /// Writes the recording next to `name`: `name.gif` or `name/frame_00000.png` and onwards.
+    /// Does nothing when the recorder is disabled.
+    pub fn save(
+        &self,
+        name: impl AsRef<Path>,
+        scale: usize,
+        palette: impl Fn(char) -> Rgb,
+    ) -> io::Result<()> {
+        let name = name.as_ref();
+        match self.output {
+            None => Ok(()),
+            Some(Output::Gif) => {
+                let mut writer = BufWriter::new(File::create(name.with_extension("gif"))?);
+                self.write_gif(&mut writer, scale, palette, 10)
+            }
+            Some(Output::Frames) => self.write_frames(name, scale, palette),
+        }
+    }
+
+    /// Writes every frame as a numbered png file in the directory
+    pub fn write_frames(
+        &self,
+        directory: impl AsRef<Path>,
+        scale: usize,
+        palette: impl Fn(char) -> Rgb,
+    ) -> io::Result<()> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            Image::from_grid(frame, scale, &palette)
+                .save(directory.join(format!("frame_{i:05}.png")))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a looping animated gif, the delay between frames is in hundredths of a second.
+    /// The palette may produce at most 256 different colours.
+    pub fn write_gif(
+        &self,
+        writer: &mut impl Write,
+        scale: usize,
+        palette: impl Fn(char) -> Rgb,
+        delay: u16,
+    ) -> io::Result<()> {
+        let images: Vec<Image> = self
+            .frames
+            .iter()
+            .map(|frame| Image::from_grid(frame, scale, &palette))
+            .collect();
+        let (width, height) = images
+            .first()
+            .map_or((0, 0), |image| (image.width(), image.height()));
+
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Gif images can be at most 65535 pixels wide and high",
+            ));
+        }
+
+        let mut colours: Vec<Rgb> = Vec::new();
+        let mut indices: HashMap<Rgb, u8> = HashMap::new();
+        for pixel in images.iter().flat_map(|image| image.pixels()) {
+            if !indices.contains_key(pixel) {
+                if colours.len() == 256 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "Gif palette is limited to 256 colours",
+                    ));
+                }
+                indices.insert(*pixel, colours.len() as u8);
+                colours.push(*pixel);
+            }
+        }
+
+        // The colour table holds a power of two entries, at least 2
+        let table_bits =
+            (usize::BITS - colours.len().max(2).saturating_sub(1).leading_zeros()) as u8;
+        colours.resize(1 << table_bits, Rgb::BLACK);
+
+        writer.write_all(b"GIF89a")?;
+        writer.write_all(&(width as u16).to_le_bytes())?;
+        writer.write_all(&(height as u16).to_le_bytes())?;
+        writer.write_all(&[0x80 | ((table_bits - 1) << 4) | (table_bits - 1), 0, 0])?;
+        for colour in &colours {
+            writer.write_all(&[colour.0, colour.1, colour.2])?;
+        }
+
+        // Netscape extension to loop forever
+        writer.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        let min_code_size = table_bits.max(2);
+        for image in &images {
+            writer.write_all(&[0x21, 0xf9, 0x04, 0x00])?;
+            writer.write_all(&delay.to_le_bytes())?;
+            writer.write_all(&[0x00, 0x00])?;
+
+            writer.write_all(&[0x2c, 0, 0, 0, 0])?;
+            writer.write_all(&(width as u16).to_le_bytes())?;
+            writer.write_all(&(height as u16).to_le_bytes())?;
+            writer.write_all(&[0x00])?;
+
+            let pixels: Vec<u8> = image.pixels().iter().map(|pixel| indices[pixel]).collect();
+            writer.write_all(&[min_code_size])?;
+            for block in lzw_encode(&pixels, min_code_size).chunks(255) {
+                writer.write_all(&[block.len() as u8])?;
+                writer.write_all(block)?;
+            }
+            writer.write_all(&[0x00])?;
+        }
+
+        writer.write_all(&[0x3b])
+    }
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Variable code size LZW as used by gif, the table is cleared when all 4096 codes are in use
+fn lzw_encode(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODES: u16 = 4096;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut next = end + 1;
+    let mut size = min_code_size + 1;
+
+    let mut writer = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        bits: 0,
+    };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+
+    writer.write(clear, size);
+
+    let mut prefix: Option<u16> = None;
+    for &pixel in pixels {
+        let Some(current) = prefix else {
+            prefix = Some(pixel as u16);
+            continue;
+        };
+
+        if let Some(code) = table.get(&(current, pixel)) {
+            prefix = Some(*code);
+            continue;
+        }
+
+        writer.write(current, size);
+        if next < MAX_CODES {
+            table.insert((current, pixel), next);
+            next += 1;
+            if next > (1 << size) && size < 12 {
+                size += 1;
+            }
+        } else {
+            writer.write(clear, size);
+            table.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        }
+        prefix = Some(pixel as u16);
+    }
+
+    if let Some(current) = prefix {
+        writer.write(current, size);
+    }
+    writer.write(end, size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference gif decoder for the codes produced by `lzw_encode`
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut size = min_code_size + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|i| vec![i as u8]).collect();
+            table.push(Vec::new());
+            table.push(Vec::new());
+        };
+        reset(&mut table);
+
+        let mut output = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        let (mut buffer, mut bits, mut index) = (0u32, 0u8, 0);
+
+        loop {
+            while bits < size {
+                buffer |= (bytes[index] as u32) << bits;
+                index += 1;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << size) - 1)) as usize;
+            buffer >>= size;
+            bits -= size;
+
+            if code == clear {
+                reset(&mut table);
+                size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+
+            let entry = match (&previous, table.get(code)) {
+                (_, Some(entry)) => entry.clone(),
+                (Some(prev), None) => [prev.clone(), vec![prev[0]]].concat(),
+                (None, None) => panic!("Invalid code"),
+            };
+            if let Some(prev) = previous.filter(|_| table.len() < 4096) {
+                table.push([prev, vec![entry[0]]].concat());
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            output.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        // Pseudo random pixels with runs so the table both grows and gets cleared
+        let mut state = 12345u32;
+        let pixels: Vec<u8> = (0..50_000)
+            .map(|i| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                if i % 7 < 3 {
+                    1
+                } else {
+                    ((state >> 16) % 4) as u8
+                }
+            })
+            .collect();
+
+        for min_code_size in [2, 3, 8] {
+            let encoded = lzw_encode(&pixels, min_code_size);
+            assert_eq!(lzw_decode(&encoded, min_code_size), pixels);
+        }
+        assert_eq!(lzw_decode(&lzw_encode(&[], 2), 2), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn disabled_records_nothing() {
+        let mut recorder = Recorder::disabled();
+        recorder.capture(&Grid::new(2, 2, '.'));
+        assert!(recorder.is_empty());
+        assert!(recorder.save("unused", 1, |_| Rgb::BLACK).is_ok());
+    }
+}
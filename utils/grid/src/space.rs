@@ -0,0 +1,486 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use utils::rational::Rational;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Position3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Position3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn distance_xyz(&self, other: Self) -> Distance3 {
+        *self - other
+    }
+
+    pub fn manhattan(&self, other: Self) -> i64 {
+        (*self - other).manhattan()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Distance3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Distance3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn six_directions() -> &'static [Self; 6] {
+        const DIRECTIONS: [Distance3; 6] = [
+            Distance3 { x: -1, y: 0, z: 0 },
+            Distance3 { x: 1, y: 0, z: 0 },
+            Distance3 { x: 0, y: -1, z: 0 },
+            Distance3 { x: 0, y: 1, z: 0 },
+            Distance3 { x: 0, y: 0, z: -1 },
+            Distance3 { x: 0, y: 0, z: 1 },
+        ];
+        &DIRECTIONS
+    }
+
+    pub fn manhattan(&self) -> i64 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn dot(&self, other: Self) -> i128 {
+        self.x as i128 * other.x as i128
+            + self.y as i128 * other.y as i128
+            + self.z as i128 * other.z as i128
+    }
+
+    /// Returns the cross product, in `i128` because the components of two large vectors overflow an `i64`
+    pub fn cross(&self, other: Self) -> [i128; 3] {
+        let (ax, ay, az) = (self.x as i128, self.y as i128, self.z as i128);
+        let (bx, by, bz) = (other.x as i128, other.y as i128, other.z as i128);
+        [ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx]
+    }
+
+    fn components(&self) -> [i128; 3] {
+        [self.x as i128, self.y as i128, self.z as i128]
+    }
+}
+
+impl Add<Distance3> for Position3 {
+    type Output = Self;
+
+    fn add(self, rhs: Distance3) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl Sub for Position3 {
+    type Output = Distance3;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl Sub<Distance3> for Position3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Distance3) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl AddAssign<Distance3> for Position3 {
+    fn add_assign(&mut self, rhs: Distance3) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl SubAssign<Distance3> for Position3 {
+    fn sub_assign(&mut self, rhs: Distance3) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl Add for Distance3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl Sub for Distance3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl Mul<i64> for Distance3 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl Neg for Distance3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+/// How far a [`Line`] extends along its direction
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Extent {
+    /// Infinite in both directions
+    Line,
+    /// Starts at the origin and is infinite in the direction
+    Ray,
+    /// Starts at the origin and ends at origin + direction
+    Segment,
+}
+
+/// The points on a line are `origin + t * direction` with `t` limited by the [`Extent`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Line {
+    pub origin: Position3,
+    pub direction: Distance3,
+    pub extent: Extent,
+}
+
+/// The exact point where two lines cross, `t` and `u` are the parameters on the first and second line
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Intersection {
+    pub t: Rational,
+    pub u: Rational,
+    pub point: [Rational; 3],
+}
+
+impl Line {
+    pub fn infinite(origin: Position3, direction: Distance3) -> Self {
+        Self {
+            origin,
+            direction,
+            extent: Extent::Line,
+        }
+    }
+
+    pub fn ray(origin: Position3, direction: Distance3) -> Self {
+        Self {
+            origin,
+            direction,
+            extent: Extent::Ray,
+        }
+    }
+
+    pub fn segment(start: Position3, end: Position3) -> Self {
+        Self {
+            origin: start,
+            direction: end - start,
+            extent: Extent::Segment,
+        }
+    }
+
+    pub fn contains_parameter(&self, t: Rational) -> bool {
+        match self.extent {
+            Extent::Line => true,
+            Extent::Ray => t >= Rational::zero(),
+            Extent::Segment => t >= Rational::zero() && t <= Rational::from(1),
+        }
+    }
+
+    pub fn point_at(&self, t: Rational) -> [Rational; 3] {
+        let origin = [self.origin.x, self.origin.y, self.origin.z];
+        let direction = [self.direction.x, self.direction.y, self.direction.z];
+        std::array::from_fn(|i| Rational::from(origin[i]) + t * Rational::from(direction[i]))
+    }
+
+    /// Intersects the projections of both lines on the xy plane, the z of the point is taken from `self`.
+    /// Parallel lines never intersect, even when they overlap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use grid::space::{Distance3, Line, Position3};
+    /// use utils::rational::Rational;
+    ///
+    /// let a = Line::ray(Position3::new(19, 13, 30), Distance3::new(-2, 1, -2));
+    /// let b = Line::ray(Position3::new(18, 19, 22), Distance3::new(-1, -1, -2));
+    /// let intersection = a.intersect_xy(&b).unwrap();
+    /// assert_eq!(intersection.point[0], Rational::new(43, 3));
+    /// assert_eq!(intersection.point[1], Rational::new(46, 3));
+    /// ```
+    pub fn intersect_xy(&self, other: &Self) -> Option<Intersection> {
+        let (d1, d2) = (self.direction, other.direction);
+        let w = other.origin - self.origin;
+
+        let den = d1.x as i128 * d2.y as i128 - d1.y as i128 * d2.x as i128;
+        if den == 0 {
+            return None;
+        }
+
+        let t = Rational::new(w.x as i128 * d2.y as i128 - w.y as i128 * d2.x as i128, den);
+        let u = Rational::new(w.x as i128 * d1.y as i128 - w.y as i128 * d1.x as i128, den);
+
+        self.intersection_at(other, t, u)
+    }
+
+    /// Intersects both lines in 3D, returns `None` for parallel or skew lines
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use grid::space::{Line, Position3};
+    /// use utils::rational::Rational;
+    ///
+    /// let a = Line::segment(Position3::new(0, 0, 0), Position3::new(2, 2, 2));
+    /// let b = Line::segment(Position3::new(2, 0, 0), Position3::new(0, 2, 2));
+    /// let intersection = a.intersect(&b).unwrap();
+    /// assert_eq!(intersection.point, [Rational::from(1); 3]);
+    ///
+    /// let c = Line::segment(Position3::new(2, 0, 5), Position3::new(0, 2, 5));
+    /// assert_eq!(a.intersect(&c), None);
+    /// ```
+    pub fn intersect(&self, other: &Self) -> Option<Intersection> {
+        let (d1, d2) = (self.direction, other.direction);
+        let w = other.origin - self.origin;
+
+        let normal = d1.cross(d2);
+        let normal_length = dot(normal, normal);
+        if normal_length == 0 || dot(w.components(), normal) != 0 {
+            return None;
+        }
+
+        let t = Rational::new(dot(w.cross(d2), normal), normal_length);
+        let u = Rational::new(dot(w.cross(d1), normal), normal_length);
+
+        self.intersection_at(other, t, u)
+    }
+
+    fn intersection_at(&self, other: &Self, t: Rational, u: Rational) -> Option<Intersection> {
+        if !self.contains_parameter(t) || !other.contains_parameter(u) {
+            return None;
+        }
+
+        Some(Intersection {
+            t,
+            u,
+            point: self.point_at(t),
+        })
+    }
+}
+
+fn dot(a: [i128; 3], b: [i128; 3]) -> i128 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// An axis aligned bounding box, both corners are inclusive
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Aabb {
+    pub min: Position3,
+    pub max: Position3,
+}
+
+impl Aabb {
+    pub fn new(a: Position3, b: Position3) -> Self {
+        Self {
+            min: Position3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Position3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// Returns the smallest box containing all the points, or `None` when there are no points
+    pub fn from_points(points: impl IntoIterator<Item = Position3>) -> Option<Self> {
+        points.into_iter().fold(None, |aabb, point| {
+            Some(match aabb {
+                None => Self::new(point, point),
+                Some(aabb) => aabb.union(&Self::new(point, point)),
+            })
+        })
+    }
+
+    pub fn contains(&self, pos: Position3) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x)
+            && (self.min.y..=self.max.y).contains(&pos.y)
+            && (self.min.z..=self.max.z).contains(&pos.z)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Position3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Position3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: Position3::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Position3::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    /// Returns the amount of integer positions inside the box
+    pub fn volume(&self) -> i128 {
+        let size = self.max - self.min;
+        (size.x as i128 + 1) * (size.y as i128 + 1) * (size.z as i128 + 1)
+    }
+}
+
+impl Add<Distance3> for Aabb {
+    type Output = Self;
+
+    fn add(self, rhs: Distance3) -> Self::Output {
+        Self {
+            min: self.min + rhs,
+            max: self.max + rhs,
+        }
+    }
+}
+
+impl Sub<Distance3> for Aabb {
+    type Output = Self;
+
+    fn sub(self, rhs: Distance3) -> Self::Output {
+        Self {
+            min: self.min - rhs,
+            max: self.max - rhs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_xy() {
+        let a = Line::segment(Position3::new(0, 0, 0), Position3::new(4, 4, 0));
+        let b = Line::segment(Position3::new(0, 4, 0), Position3::new(4, 0, 0));
+        let c = Line::segment(Position3::new(0, 4, 0), Position3::new(1, 3, 0));
+        let d = Line::segment(Position3::new(1, 1, 0), Position3::new(5, 5, 0));
+
+        let intersection = a.intersect_xy(&b).unwrap();
+        assert_eq!(intersection.t, Rational::new(1, 2));
+        assert_eq!(intersection.point[0], Rational::from(2));
+        assert_eq!(a.intersect_xy(&c), None);
+        assert_eq!(
+            Line::infinite(c.origin, c.direction)
+                .intersect_xy(&a)
+                .unwrap()
+                .t,
+            Rational::from(2)
+        );
+        assert_eq!(a.intersect_xy(&d), None);
+    }
+
+    #[test]
+    fn rays_in_the_past() {
+        let a = Line::ray(Position3::new(19, 13, 30), Distance3::new(-2, 1, -2));
+        let b = Line::ray(Position3::new(20, 19, 15), Distance3::new(1, -5, -3));
+
+        assert_eq!(a.intersect_xy(&b), None);
+        assert!(
+            Line::infinite(a.origin, a.direction)
+                .intersect_xy(&Line::infinite(b.origin, b.direction))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn large_coordinates() {
+        let a = Line::ray(
+            Position3::new(400_000_000_000_000, 0, 400_000_000_000_000),
+            Distance3::new(-1000, 1000, -1000),
+        );
+        let b = Line::ray(Position3::new(0, 0, 0), Distance3::new(1000, 1000, 1000));
+
+        let intersection = a.intersect(&b).unwrap();
+        assert_eq!(
+            intersection.point[0],
+            Rational::from(200_000_000_000_000i64)
+        );
+        assert_eq!(
+            intersection.point[1],
+            Rational::from(200_000_000_000_000i64)
+        );
+    }
+
+    #[test]
+    fn aabb() {
+        let a = Aabb::new(Position3::new(0, 0, 0), Position3::new(2, 2, 2));
+        let b = Aabb::new(Position3::new(3, 2, 2), Position3::new(1, 1, 1));
+        let c = a + Distance3::new(0, 0, 3);
+
+        assert_eq!(a.volume(), 27);
+        assert_eq!(
+            a.intersection(&b),
+            Some(Aabb::new(Position3::new(1, 1, 1), Position3::new(2, 2, 2)))
+        );
+        assert!(!a.intersects(&c));
+        assert!(a.union(&c).contains(Position3::new(1, 1, 4)));
+        assert_eq!(
+            Aabb::from_points([Position3::new(1, 5, 0), Position3::new(3, -1, 2)]),
+            Some(Aabb::new(Position3::new(1, -1, 0), Position3::new(3, 5, 2)))
+        );
+    }
+}
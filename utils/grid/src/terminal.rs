@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Stdout, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::image::Rgb;
+use crate::{Grid, Position};
+
+/// How fast consecutive calls to `Terminal::draw` follow each other
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pace {
+    /// Draw as fast as possible
+    Unlimited,
+    /// Wait until the frame has been on screen for the duration
+    Frame(Duration),
+    /// Wait for enter to be pressed on stdin after every frame
+    Step,
+}
+
+impl Pace {
+    pub fn fps(frames_per_second: u32) -> Self {
+        assert!(frames_per_second > 0, "Frame rate should be at least 1");
+        Pace::Frame(Duration::from_secs(1) / frames_per_second)
+    }
+}
+
+/// The part of the grid that is shown, `origin` is the top left cell
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub origin: Position,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Draws grids with ANSI colours, every draw replaces the previous frame in place
+///
+/// # Example
+///
+/// ```
+/// use grid::{Grid, Position, image::Rgb, terminal::Terminal};
+///
+/// let grid = Grid::from_text("#..\n.@.\n..#");
+/// let terminal = Terminal::new(Vec::new())
+///     .palette(|ch| (ch == '#').then_some(Rgb::BLUE))
+///     .viewport(Position::new(1, 1), 2, 2);
+///
+/// let frame = terminal.render(&grid, &[Position::new(2, 2)].into());
+/// assert_eq!(frame, "@.\n.\x1b[48;2;255;0;0m\x1b[38;2;0;0;255m#\x1b[0m\n");
+/// ```
+pub struct Terminal<W: Write> {
+    writer: W,
+    palette: Box<dyn Fn(char) -> Option<Rgb>>,
+    highlight: Rgb,
+    pace: Pace,
+    viewport: Option<Viewport>,
+    lines_drawn: usize,
+    last_draw: Option<Instant>,
+}
+
+impl Terminal<Stdout> {
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            palette: Box::new(|_| None),
+            highlight: Rgb::RED,
+            pace: Pace::Unlimited,
+            viewport: None,
+            lines_drawn: 0,
+            last_draw: None,
+        }
+    }
+
+    /// Sets the foreground colour per char, `None` keeps the default terminal colour
+    pub fn palette(mut self, palette: impl Fn(char) -> Option<Rgb> + 'static) -> Self {
+        self.palette = Box::new(palette);
+        self
+    }
+
+    /// Sets the background colour of highlighted positions
+    pub fn highlight(mut self, colour: Rgb) -> Self {
+        self.highlight = colour;
+        self
+    }
+
+    pub fn pace(mut self, pace: Pace) -> Self {
+        self.pace = pace;
+        self
+    }
+
+    /// Only shows the cells within the rectangle, useful for grids wider than the terminal
+    pub fn viewport(mut self, origin: Position, width: usize, height: usize) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "Viewport should be at least 1x1, got {width}x{height}"
+        );
+        self.viewport = Some(Viewport {
+            origin,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Moves the viewport the least amount needed to show the position
+    pub fn follow(&mut self, pos: Position) {
+        if let Some(viewport) = &mut self.viewport {
+            let width = viewport.width as i32;
+            let height = viewport.height as i32;
+            viewport.origin.x = viewport.origin.x.clamp(pos.x - width + 1, pos.x);
+            viewport.origin.y = viewport.origin.y.clamp(pos.y - height + 1, pos.y);
+        }
+    }
+
+    /// Returns the frame as text with escape codes, lines end with a newline
+    pub fn render(&self, grid: &Grid, highlights: &HashSet<Position>) -> String {
+        let viewport = self.viewport.unwrap_or(Viewport {
+            origin: Position::new(0, 0),
+            width: grid.width(),
+            height: grid.height(),
+        });
+
+        let mut output = String::new();
+        for y in viewport.origin.y..viewport.origin.y + viewport.height as i32 {
+            if y < 0 || y >= grid.height() as i32 {
+                continue;
+            }
+            for x in viewport.origin.x..viewport.origin.x + viewport.width as i32 {
+                let pos = Position::new(x, y);
+                let Some(ch) = grid.get(pos) else {
+                    continue;
+                };
+
+                let highlighted = highlights.contains(&pos);
+                let colour = (self.palette)(ch);
+                if highlighted {
+                    let Rgb(r, g, b) = self.highlight;
+                    let _ = write!(output, "\x1b[48;2;{r};{g};{b}m");
+                }
+                if let Some(Rgb(r, g, b)) = colour {
+                    let _ = write!(output, "\x1b[38;2;{r};{g};{b}m");
+                }
+                output.push(ch);
+                if highlighted || colour.is_some() {
+                    output.push_str("\x1b[0m");
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Draws the frame over the previous one after waiting for the configured pace
+    pub fn draw(&mut self, grid: &Grid, highlights: &HashSet<Position>) -> io::Result<()> {
+        match self.pace {
+            Pace::Unlimited => (),
+            Pace::Frame(duration) => {
+                if let Some(elapsed) = self.last_draw.map(|last| last.elapsed()) {
+                    thread::sleep(duration.saturating_sub(elapsed));
+                }
+            }
+            Pace::Step => {
+                if self.last_draw.is_some() {
+                    io::stdin().lock().read_line(&mut String::new())?;
+                    // The enter key moved the cursor down one line
+                    self.lines_drawn += 1;
+                }
+            }
+        }
+
+        let frame = self.render(grid, highlights);
+        if self.lines_drawn > 0 {
+            // Move to the start of the previous frame and clear everything below
+            write!(self.writer, "\x1b[{}F\x1b[J", self.lines_drawn)?;
+        }
+        self.writer.write_all(frame.as_bytes())?;
+        self.writer.flush()?;
+
+        self.lines_drawn = frame.lines().count();
+        self.last_draw = Some(Instant::now());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redraws_in_place() {
+        let grid = Grid::from_text("ab\ncd");
+        let mut terminal = Terminal::new(Vec::new());
+
+        terminal.draw(&grid, &HashSet::new()).unwrap();
+        terminal.draw(&grid, &HashSet::new()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(terminal.writer).unwrap(),
+            "ab\ncd\n\x1b[2F\x1b[Jab\ncd\n"
+        );
+    }
+
+    #[test]
+    fn follow_keeps_position_visible() {
+        let grid = Grid::from_text("abcd\nefgh\nijkl");
+        let mut terminal = Terminal::new(Vec::new()).viewport(Position::new(0, 0), 2, 2);
+
+        terminal.follow(Position::new(3, 2));
+        assert_eq!(terminal.render(&grid, &HashSet::new()), "gh\nkl\n");
+
+        terminal.follow(Position::new(2, 0));
+        assert_eq!(terminal.render(&grid, &HashSet::new()), "cd\ngh\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "Viewport should be at least 1x1, got 0x3")]
+    fn empty_viewport() {
+        let _ = Terminal::new(Vec::new()).viewport(Position::new(0, 0), 0, 3);
+    }
+}
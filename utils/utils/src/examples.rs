@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+use crate::params::Params;
+
+/// An example input with the parameters from its answers file, see the `example_tests` macro
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub name: &'static str,
+    pub path: &'static str,
+    pub input: &'static str,
+    pub params: Params,
+}
+
+/// Runs the solver on the example and compares the displayed answer with the expected one
+///
+/// # Example
+///
+/// ```
+/// use utils::examples::{check, Example};
+/// use utils::params::Params;
+///
+/// let example = Example {
+///     name: "example",
+///     path: "src/example.txt",
+///     input: "1\n2\n3",
+///     params: Params::parse(["--param", "factor=2"]).unwrap(),
+/// };
+///
+/// check(&example, "12", |example| {
+///     let factor: u64 = example.params.get("factor", 1);
+///     example.input.lines().map(|nr| nr.parse::<u64>().unwrap() * factor).sum::<u64>()
+/// });
+/// ```
+pub fn check<R: Display>(example: &Example, expected: &str, solve: impl Fn(&Example) -> R) {
+    let answer = solve(example).to_string();
+    assert_eq!(answer, expected, "Wrong answer for {}", example.name);
+}
@@ -1,3 +1,12 @@
+pub mod examples;
+pub mod memo;
+pub mod par;
+pub mod params;
+pub mod pattern;
+pub mod random;
+pub mod rational;
+pub mod sequence;
+
 use std::collections::HashMap;
 
 /// Returns an iterator over all the combinations of the input
@@ -38,19 +47,59 @@ pub fn least_common_multiple(first: u64, second: u64) -> u64 {
 /// use utils::greatest_common_divisor;
 ///
 /// assert_eq!(greatest_common_divisor(72, 27), 9);
+/// assert_eq!(greatest_common_divisor(5, 0), 5);
 /// ```
 pub fn greatest_common_divisor(first: u64, second: u64) -> u64 {
     let mut max = first.max(second);
     let mut min = first.min(second);
 
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
+    while min != 0 {
+        (max, min) = (min, max % min);
+    }
+    max
+}
+
+/// Returns the smallest non negative x that satisfies `x % modulus == residue` for every pair,
+/// together with the period after which the solution repeats. Returns None if there is no solution.
+///
+/// # Example
+///
+/// ```
+/// use utils::chinese_remainder;
+///
+/// assert_eq!(chinese_remainder(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(chinese_remainder(&[(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+/// ```
+pub fn chinese_remainder(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut result = 0i128;
+    let mut period = 1i128;
+
+    for (residue, modulus) in congruences {
+        let (residue, modulus) = (*residue as i128, *modulus as i128);
+        let (divisor, inverse, _) = extended_gcd(period, modulus);
+        let difference = residue - result;
+        if difference % divisor != 0 {
+            return None;
         }
 
-        max = min;
-        min = res;
+        let step = modulus / divisor;
+        let factor = (difference / divisor * inverse).rem_euclid(step);
+        result += period * factor;
+        period *= step;
+        result = result.rem_euclid(period);
+    }
+
+    Some((result as i64, period as i64))
+}
+
+/// Returns (gcd, x, y) such that a * x + b * y == gcd
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (divisor, x, y) = extended_gcd(b, a % b);
+        (divisor, y, x - (a / b) * y)
     }
 }
 
@@ -109,14 +158,14 @@ pub fn is_prime(mut n: u64) -> bool {
 pub fn prime_factors(mut n: u64) -> HashMap<u64, u64> {
     let mut map = HashMap::new();
 
-    while n % 2 == 0 {
+    while n.is_multiple_of(2) {
         *map.entry(2).or_default() += 1;
         n /= 2;
     }
 
     let mut i = 3;
     while i <= (n as f64).sqrt() as u64 {
-        while n % i == 0 {
+        while n.is_multiple_of(i) {
             *map.entry(i).or_default() += 1;
             n /= i;
         }
@@ -176,11 +225,12 @@ pub fn gaussian_elimination(matrix: &[Vec<f64>]) -> Vec<f64> {
             matrix[i_max] = matrix[h].clone();
             matrix[h] = copy;
 
-            for i in (h + 1)..m {
-                let f = matrix[i][k] / matrix[h][k];
-                matrix[i][k] = 0f64;
-                for j in (k + 1)..n {
-                    matrix[i][j] -= matrix[h][j] * f;
+            let pivot = matrix[h].clone();
+            for row in matrix[(h + 1)..m].iter_mut() {
+                let f = row[k] / pivot[k];
+                row[k] = 0f64;
+                for (value, pivot) in row[(k + 1)..n].iter_mut().zip(&pivot[(k + 1)..n]) {
+                    *value -= pivot * f;
                 }
             }
             h += 1;
@@ -236,6 +286,7 @@ pub fn gaussian_elimination_int(matrix: &[Vec<i64>]) -> Option<Vec<i64>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::random::Rng;
 
     #[test]
     fn test_prime_factors() {
@@ -247,4 +298,73 @@ mod tests {
                 .collect()
         );
     }
+
+    #[test]
+    fn divisor_identities() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let a = rng.range(1..100_000) as u64;
+            let b = rng.range(1..100_000) as u64;
+            let gcd = greatest_common_divisor(a, b);
+            let lcm = least_common_multiple(a, b);
+
+            assert_eq!(a % gcd, 0);
+            assert_eq!(b % gcd, 0);
+            assert_eq!(greatest_common_divisor(a / gcd, b / gcd), 1);
+            assert_eq!(gcd * lcm, a * b);
+            assert_eq!(greatest_common_divisor(b, a), gcd);
+            assert_eq!(greatest_common_divisor(a, a), a);
+        }
+    }
+
+    #[test]
+    fn primes_agree_with_factors() {
+        let mut rng = Rng::new(2);
+        let small = 1..2000;
+        let large = (0..1000).map(|_| rng.range(1..1_000_000_000) as u64);
+
+        for n in small.chain(large) {
+            let factors = prime_factors(n);
+            let product: u64 = factors
+                .iter()
+                .map(|(p, count)| p.pow(*count as u32))
+                .product();
+
+            assert_eq!(product, n);
+            assert!(factors.keys().all(|p| is_prime(*p)));
+            assert_eq!(is_prime(n), factors == HashMap::from([(n, 1)]), "{n}");
+        }
+    }
+
+    #[test]
+    fn gaussian_elimination_solves_system() {
+        let mut rng = Rng::new(3);
+        for _ in 0..200 {
+            let size = rng.range(1..7) as usize;
+            let solution: Vec<i64> = (0..size).map(|_| rng.range(-50..50)).collect();
+
+            // A diagonally dominant matrix is never singular
+            let mut matrix: Vec<Vec<i64>> = (0..size)
+                .map(|_| (0..size).map(|_| rng.range(-9..10)).collect())
+                .collect();
+            for (i, row) in matrix.iter_mut().enumerate() {
+                let others: i64 = row.iter().map(|value| value.abs()).sum::<i64>() - row[i].abs();
+                row[i] = (others + rng.range(1..10)) * if rng.chance(0.5) { 1 } else { -1 };
+                let constant = row.iter().zip(&solution).map(|(a, x)| a * x).sum();
+                row.push(constant);
+            }
+
+            let float_matrix: Vec<Vec<f64>> = matrix
+                .iter()
+                .map(|row| row.iter().map(|value| *value as f64).collect())
+                .collect();
+            let result = gaussian_elimination(&float_matrix);
+            for row in &float_matrix {
+                let lhs: f64 = row.iter().zip(&result).map(|(a, x)| a * x).sum();
+                assert!((lhs - row[size]).abs() < 1e-6, "{row:?} {result:?}");
+            }
+
+            assert_eq!(gaussian_elimination_int(&matrix), Some(solution));
+        }
+    }
 }
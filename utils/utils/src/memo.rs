@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cache for recursive solvers, the compute closure gets the memo back so it can recurse
+/// without fighting the borrow checker
+///
+/// # Example
+///
+/// ```
+/// use utils::memo::Memo;
+///
+/// fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///     memo.get_or_compute(n, |memo| {
+///         if n < 2 {
+///             return n;
+///         }
+///         fibonacci(n - 1, memo) + fibonacci(n - 2, memo)
+///     })
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+///
+/// let stats = memo.stats();
+/// assert_eq!(stats.misses, 91);
+/// assert_eq!(stats.hits, 88);
+/// assert_eq!(stats.entries, 91);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl MemoStats {
+    /// Returns the fraction of lookups that were answered from the cache
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0f64
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for the key or computes and stores it
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Removes all cached values and resets the statistics
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+}
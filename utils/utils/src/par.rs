@@ -0,0 +1,163 @@
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Environment variable that overrides the amount of threads used by `Pool::from_env`
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+/// Runs independent work on scoped threads and returns the results in the order of the input
+///
+/// Threads take the next index from a shared counter, so items that take a lot longer than
+/// others don't leave the remaining threads idle.
+///
+/// # Example
+///
+/// ```
+/// use utils::par::Pool;
+///
+/// let pool = Pool::new(4);
+/// let words = ["a", "bb", "ccc"];
+///
+/// assert_eq!(pool.map(&words, |word| word.len()), [1, 2, 3]);
+/// assert_eq!(pool.sum_range(0..1000, |i| i as u64), 499500);
+/// assert_eq!(Pool::new(1).map_range(0..3, |i| i * 2), [0, 2, 4]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pool {
+    threads: usize,
+}
+
+impl Default for Pool {
+    /// Uses one thread per available core
+    fn default() -> Self {
+        Self::new(thread::available_parallelism().map_or(1, NonZeroUsize::get))
+    }
+}
+
+impl Pool {
+    /// Creates a pool with the given amount of threads, a single thread runs the work inline
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+        }
+    }
+
+    /// Creates a pool with the amount of threads from `AOC_THREADS`, or one per core
+    pub fn from_env() -> Self {
+        match std::env::var(THREADS_VAR) {
+            Ok(threads) => Self::new(
+                threads
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{THREADS_VAR} should be a number, got {threads}")),
+            ),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    pub fn map<T, R, F>(&self, items: &[T], f: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        self.run(items.len(), |i| f(&items[i]))
+    }
+
+    pub fn map_range<R, F>(&self, range: Range<usize>, f: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(usize) -> R + Sync,
+    {
+        let start = range.start;
+        self.run(range.len(), |i| f(start + i))
+    }
+
+    pub fn sum<T, R, F>(&self, items: &[T], f: F) -> R
+    where
+        T: Sync,
+        R: Send + std::iter::Sum,
+        F: Fn(&T) -> R + Sync,
+    {
+        self.map(items, f).into_iter().sum()
+    }
+
+    pub fn sum_range<R, F>(&self, range: Range<usize>, f: F) -> R
+    where
+        R: Send + std::iter::Sum,
+        F: Fn(usize) -> R + Sync,
+    {
+        self.map_range(range, f).into_iter().sum()
+    }
+
+    fn run<R, F>(&self, len: usize, f: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(usize) -> R + Sync,
+    {
+        let threads = self.threads.min(len);
+        if threads <= 1 {
+            return (0..len).map(f).collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let worker = || {
+            let mut results = Vec::new();
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= len {
+                    return results;
+                }
+                results.push((i, f(i)));
+            }
+        };
+
+        let mut slots: Vec<Option<R>> = (0..len).map(|_| None).collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads).map(|_| scope.spawn(worker)).collect();
+            for handle in handles {
+                let results = handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                for (i, result) in results {
+                    slots[i] = Some(result);
+                }
+            }
+        });
+
+        slots
+            .into_iter()
+            .map(|result| result.expect("Every index to be computed"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order_for_uneven_work() {
+        let items: Vec<u64> = (0..200).collect();
+        let expected: Vec<u64> = items.iter().map(|i| i * i).collect();
+
+        for threads in [1, 2, 3, 8, 500] {
+            let pool = Pool::new(threads);
+            let squares = pool.map(&items, |i| {
+                // Early items take longer so threads finish out of order
+                if *i < 10 {
+                    thread::sleep(std::time::Duration::from_millis(2));
+                }
+                i * i
+            });
+            assert_eq!(squares, expected, "{threads} threads");
+        }
+
+        assert_eq!(Pool::new(4).map_range(5..5, |i| i), Vec::<usize>::new());
+        assert_eq!(Pool::new(0).threads(), 1);
+    }
+}
@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::str::FromStr;
+
+/// Named puzzle constants and the input file, taken from the command line
+///
+/// Days pass the values for the real puzzle as defaults, so running without arguments solves the
+/// puzzle while an example can be run through the same entry point:
+///
+/// ```text
+/// cargo run -p year2024_day14 -- --input src/example.txt --param width=11 --param height=7
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use utils::params::Params;
+///
+/// let params = Params::parse(["--param", "steps=6", "--param=brute_force=true"]).unwrap();
+///
+/// assert_eq!(params.get("steps", 64), 6);
+/// assert_eq!(params.get("brute_force", false), true);
+/// assert_eq!(params.get("width", 101), 101);
+/// assert_eq!(params.input("puzzle contents"), "puzzle contents");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: HashMap<String, String>,
+    input: Option<String>,
+}
+
+impl Params {
+    /// Parses the arguments of the current process, exits with a usage message when they are
+    /// not understood
+    pub fn from_args() -> Self {
+        Self::parse(std::env::args().skip(1)).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            eprintln!("Usage: [--input <file>] [--param <name>=<value>]...");
+            std::process::exit(2);
+        })
+    }
+
+    pub fn parse<I, S>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut params = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg, None),
+            };
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().map(|value| value.as_ref().to_string()))
+                    .ok_or(format!("Missing value for {flag}"))
+            };
+
+            match flag {
+                "--input" => params.input = Some(value()?),
+                "--param" => {
+                    let value = value()?;
+                    let (name, value) = value
+                        .split_once('=')
+                        .ok_or(format!("Parameter should be <name>=<value>, got {value}"))?;
+                    params.values.insert(name.to_string(), value.to_string());
+                }
+                _ => return Err(format!("Unknown argument {arg}")),
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Returns the parameter, or the default when it was not given
+    ///
+    /// # Panics
+    ///
+    /// When the given value can't be parsed as `T`
+    pub fn get<T>(&self, name: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        match self.values.get(name) {
+            Some(value) => value
+                .parse()
+                .unwrap_or_else(|error| panic!("Invalid value {value} for {name}: {error:?}")),
+            None => default,
+        }
+    }
+
+    /// Returns the contents of the `--input` file, or the default contents
+    pub fn input<'a>(&self, default: &'a str) -> Cow<'a, str> {
+        match &self.input {
+            Some(path) => Cow::Owned(
+                fs::read_to_string(path)
+                    .unwrap_or_else(|error| panic!("Can't read {path}: {error}")),
+            ),
+            None => Cow::Borrowed(default),
+        }
+    }
+
+    /// Returns the path of the `--input` file, or the default path
+    pub fn input_path<'a>(&'a self, default: &'a str) -> &'a str {
+        self.input.as_deref().unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(
+            Params::parse(["--param"]),
+            Err("Missing value for --param".to_string())
+        );
+        assert!(Params::parse(["--param", "steps"]).is_err());
+        assert!(Params::parse(["steps=6"]).is_err());
+
+        let params = Params::parse(["--input=example.txt", "--param", "x=a=b"]).unwrap();
+        assert_eq!(params.input_path("puzzle.txt"), "example.txt");
+        assert_eq!(params.get("x", String::new()), "a=b");
+    }
+}
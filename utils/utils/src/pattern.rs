@@ -0,0 +1,290 @@
+/// A small backtracking pattern, built from literals, bounded digit runs, sequences, alternations
+/// and captures
+///
+/// # Example
+///
+/// ```
+/// use utils::pattern::Pattern;
+///
+/// let number = Pattern::capture(Pattern::digits(1, 3));
+/// let mul = Pattern::sequence([
+///     Pattern::literal("mul("),
+///     number.clone(),
+///     Pattern::literal(","),
+///     number,
+///     Pattern::literal(")"),
+/// ]);
+///
+/// let found = mul.find("xmul(2,4)%&mul[3,7]").unwrap();
+/// assert_eq!((found.start, found.text), (1, "mul(2,4)"));
+/// assert_eq!(found.captures, ["2", "4"]);
+/// assert!(mul.find("mul(1234,5)").is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    Literal(String),
+    /// A run of ascii digits, as long as possible within the bounds
+    Digits {
+        min: usize,
+        max: usize,
+    },
+    Sequence(Vec<Pattern>),
+    /// The first option that leads to a match is used
+    Alternation(Vec<Pattern>),
+    Capture(Box<Pattern>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match<'a> {
+    pub start: usize,
+    pub end: usize,
+    pub text: &'a str,
+    /// The captured texts in the order the captures finished matching
+    pub captures: Vec<&'a str>,
+}
+
+type Span = (usize, usize);
+
+impl Pattern {
+    pub fn literal(text: &str) -> Self {
+        Pattern::Literal(text.to_string())
+    }
+
+    pub fn digits(min: usize, max: usize) -> Self {
+        assert!(min <= max, "Minimum digits should not exceed the maximum");
+        Pattern::Digits { min, max }
+    }
+
+    pub fn sequence(patterns: impl IntoIterator<Item = Pattern>) -> Self {
+        Pattern::Sequence(patterns.into_iter().collect())
+    }
+
+    pub fn alternation(patterns: impl IntoIterator<Item = Pattern>) -> Self {
+        Pattern::Alternation(patterns.into_iter().collect())
+    }
+
+    pub fn capture(pattern: Pattern) -> Self {
+        Pattern::Capture(Box::new(pattern))
+    }
+
+    /// Matches the pattern starting exactly at `start`
+    pub fn match_at<'a>(&self, input: &'a str, start: usize) -> Option<Match<'a>> {
+        let mut captures = Vec::new();
+        let mut found = None;
+
+        self.match_from(input, start, &mut captures, &mut |end, captures| {
+            found = Some((end, captures.clone()));
+            true
+        });
+
+        found.map(|(end, spans)| Match {
+            start,
+            end,
+            text: &input[start..end],
+            captures: spans.iter().map(|(from, to)| &input[*from..*to]).collect(),
+        })
+    }
+
+    /// Returns the first match in the input
+    pub fn find<'a>(&self, input: &'a str) -> Option<Match<'a>> {
+        input
+            .char_indices()
+            .find_map(|(start, _)| self.match_at(input, start))
+    }
+
+    /// Returns true when the pattern matches the whole input
+    pub fn is_match(&self, input: &str) -> bool {
+        let mut captures = Vec::new();
+        self.match_from(input, 0, &mut captures, &mut |end, _| end == input.len())
+    }
+
+    fn match_from(
+        &self,
+        input: &str,
+        pos: usize,
+        captures: &mut Vec<Span>,
+        next: &mut dyn FnMut(usize, &mut Vec<Span>) -> bool,
+    ) -> bool {
+        match self {
+            Pattern::Literal(text) => {
+                input[pos..].starts_with(text.as_str()) && next(pos + text.len(), captures)
+            }
+            Pattern::Digits { min, max } => {
+                let available = input[pos..]
+                    .bytes()
+                    .take(*max)
+                    .take_while(u8::is_ascii_digit)
+                    .count();
+                (*min..=available)
+                    .rev()
+                    .any(|len| next(pos + len, captures))
+            }
+            Pattern::Sequence(patterns) => match_sequence(patterns, input, pos, captures, next),
+            Pattern::Alternation(options) => options
+                .iter()
+                .any(|option| option.match_from(input, pos, captures, next)),
+            Pattern::Capture(inner) => {
+                inner.match_from(input, pos, captures, &mut |end, captures| {
+                    captures.push((pos, end));
+                    if next(end, captures) {
+                        true
+                    } else {
+                        captures.pop();
+                        false
+                    }
+                })
+            }
+        }
+    }
+}
+
+fn match_sequence(
+    patterns: &[Pattern],
+    input: &str,
+    pos: usize,
+    captures: &mut Vec<Span>,
+    next: &mut dyn FnMut(usize, &mut Vec<Span>) -> bool,
+) -> bool {
+    match patterns.split_first() {
+        None => next(pos, captures),
+        Some((first, rest)) => first.match_from(input, pos, captures, &mut |end, captures| {
+            match_sequence(rest, input, end, captures, next)
+        }),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token<'a, T> {
+    pub kind: T,
+    pub matched: Match<'a>,
+}
+
+/// Scans the input for several token patterns at once
+///
+/// At every position the patterns are tried in the order they were added. A match is consumed
+/// completely, so tokens never overlap, and text that matches no pattern is skipped.
+///
+/// # Example
+///
+/// ```
+/// use utils::pattern::{Pattern, Tokenizer};
+///
+/// let tokenizer = Tokenizer::new()
+///     .token('+', Pattern::literal("plus"))
+///     .token('n', Pattern::digits(1, 2));
+///
+/// let kinds: String = tokenizer.tokens("1plus234 plu5").map(|token| token.kind).collect();
+/// assert_eq!(kinds, "n+nnn");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tokenizer<T> {
+    patterns: Vec<(T, Pattern)>,
+}
+
+impl<T> Default for Tokenizer<T> {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone> Tokenizer<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn token(mut self, kind: T, pattern: Pattern) -> Self {
+        self.patterns.push((kind, pattern));
+        self
+    }
+
+    pub fn tokens<'a>(&'a self, input: &'a str) -> impl Iterator<Item = Token<'a, T>> + 'a {
+        let mut pos = 0;
+
+        std::iter::from_fn(move || {
+            while pos < input.len() {
+                // Empty matches would never advance, so they don't hide the patterns after them
+                let found = self.patterns.iter().find_map(|(kind, pattern)| {
+                    pattern
+                        .match_at(input, pos)
+                        .filter(|matched| matched.end > pos)
+                        .map(|matched| Token {
+                            kind: kind.clone(),
+                            matched,
+                        })
+                });
+
+                match found {
+                    Some(token) => {
+                        pos = token.matched.end;
+                        return Some(token);
+                    }
+                    None => pos += input[pos..].chars().next().map_or(1, char::len_utf8),
+                }
+            }
+            None
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backtracks_into_digits() {
+        // The digit run has to give back its last digit for the literal to match
+        let pattern = Pattern::sequence([
+            Pattern::capture(Pattern::digits(1, 3)),
+            Pattern::literal("3"),
+        ]);
+        assert_eq!(pattern.match_at("1233", 0).unwrap().captures, ["123"]);
+        assert_eq!(pattern.match_at("123", 0).unwrap().captures, ["12"]);
+        assert!(pattern.match_at("3", 0).is_none());
+    }
+
+    #[test]
+    fn alternation_captures() {
+        let pattern = Pattern::alternation([
+            Pattern::sequence([
+                Pattern::literal("a"),
+                Pattern::capture(Pattern::digits(2, 2)),
+            ]),
+            Pattern::capture(Pattern::sequence([
+                Pattern::literal("a"),
+                Pattern::digits(1, 1),
+            ])),
+        ]);
+        assert_eq!(pattern.match_at("a12", 0).unwrap().captures, ["12"]);
+        assert_eq!(pattern.match_at("a1x", 0).unwrap().captures, ["a1"]);
+        assert!(pattern.is_match("a12"));
+        assert!(!pattern.is_match("a123"));
+    }
+
+    #[test]
+    fn tokens_skip_unknown_text() {
+        let tokenizer = Tokenizer::new()
+            .token("do", Pattern::literal("do()"))
+            .token("dont", Pattern::literal("don't()"));
+
+        let tokens: Vec<_> = tokenizer
+            .tokens("é do() don't() do(")
+            .map(|token| (token.kind, token.matched.start))
+            .collect();
+        assert_eq!(tokens, [("do", 3), ("dont", 8)]);
+    }
+
+    #[test]
+    fn empty_matches_dont_hide_later_patterns() {
+        let tokenizer = Tokenizer::new()
+            .token("number", Pattern::digits(0, 3))
+            .token("comma", Pattern::literal(","));
+
+        let tokens: Vec<_> = tokenizer
+            .tokens("12,3")
+            .map(|token| (token.kind, token.matched.text))
+            .collect();
+        assert_eq!(tokens, [("number", "12"), ("comma", ","), ("number", "3")]);
+    }
+}
@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+/// Small deterministic random generator (SplitMix64) for randomized tests, the same seed always
+/// gives the same sequence. Not suitable for anything that has to be unpredictable.
+///
+/// # Example
+///
+/// ```
+/// use utils::random::Rng;
+///
+/// let mut rng = Rng::new(2024);
+/// let rolls: Vec<i64> = (0..100).map(|_| rng.range(1..7)).collect();
+///
+/// assert!(rolls.iter().all(|roll| (1..7).contains(roll)));
+/// assert_eq!(rolls, (0..100).map({
+///     let mut rng = Rng::new(2024);
+///     move |_| rng.range(1..7)
+/// }).collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..max`
+    pub fn below(&mut self, max: u64) -> u64 {
+        assert!(max > 0, "Range should not be empty");
+        // The modulo bias is negligible for the small ranges used in tests
+        self.next_u64() % max
+    }
+
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "Range should not be empty");
+        range.start + self.below(range.end.abs_diff(range.start)) as i64
+    }
+
+    /// Returns true with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
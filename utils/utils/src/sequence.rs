@@ -0,0 +1,220 @@
+use crate::rational::Rational;
+
+/// Returns the differences between each pair of consecutive values
+///
+/// # Example
+///
+/// ```
+/// use utils::sequence::differences;
+///
+/// assert_eq!(differences(&[1, 3, 6, 10, 15]), [2, 3, 4, 5]);
+/// ```
+pub fn differences(samples: &[i64]) -> Vec<i64> {
+    samples.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+/// Returns the samples followed by each row of differences until a row is all zeros or has a
+/// single value left
+pub fn difference_table(samples: &[i64]) -> Vec<Vec<i64>> {
+    let mut table = vec![samples.to_vec()];
+
+    while let Some(row) = table.last()
+        && row.len() > 1
+        && row.iter().any(|value| *value != 0)
+    {
+        table.push(differences(row));
+    }
+
+    table
+}
+
+/// Returns the degree of the polynomial that generates the samples, which is the amount of times
+/// differences have to be taken before they become constant. Returns `None` when the samples
+/// don't contain enough values to show the differences are constant.
+///
+/// # Example
+///
+/// ```
+/// use utils::sequence::degree;
+///
+/// assert_eq!(degree(&[3, 3, 3]), Some(0));
+/// assert_eq!(degree(&[0, 3, 6, 9, 12, 15]), Some(1));
+/// assert_eq!(degree(&[10, 13, 16, 21, 30, 45]), Some(3));
+/// assert_eq!(degree(&[1, 2, 4, 8]), None);
+/// ```
+pub fn degree(samples: &[i64]) -> Option<usize> {
+    let mut row = samples.to_vec();
+
+    for degree in 0.. {
+        if row.len() < 2 {
+            return None;
+        }
+        if row.iter().all(|value| *value == row[0]) {
+            return Some(degree);
+        }
+        row = differences(&row);
+    }
+
+    unreachable!()
+}
+
+/// Returns the value at `index` of the lowest degree polynomial through the samples, where the
+/// samples are at indices `0..samples.len()`. Negative indices extrapolate backwards.
+///
+/// # Example
+///
+/// ```
+/// use utils::sequence::extrapolate;
+///
+/// let samples = [10, 13, 16, 21, 30, 45];
+/// assert_eq!(extrapolate(&samples, 6), 68);
+/// assert_eq!(extrapolate(&samples, -1), 5);
+/// assert_eq!(extrapolate(&[1, 4, 9], 1_000_000), 1_000_002_000_001);
+/// ```
+pub fn extrapolate(samples: &[i64], index: i64) -> i128 {
+    // Newton's forward difference formula: f(n) = sum over k of binomial(n, k) * first difference k
+    let leading: Vec<i64> = difference_table(samples)
+        .iter()
+        .filter_map(|row| row.first().copied())
+        .collect();
+
+    let n = index as i128;
+    let mut binomial = 1i128;
+    let mut value = 0;
+
+    for (k, difference) in leading.iter().enumerate() {
+        if k > 0 {
+            // Always exact, binomial(n, k - 1) * (n - k + 1) equals k * binomial(n, k)
+            binomial = binomial * (n - k as i128 + 1) / k as i128;
+        }
+        value += binomial * *difference as i128;
+    }
+
+    value
+}
+
+/// A polynomial with exact rational coefficients, lowest power first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Rational>) -> Self {
+        let mut polynomial = Self { coefficients };
+        polynomial.trim();
+        polynomial
+    }
+
+    /// Fits the lowest degree polynomial through the points using Lagrange interpolation,
+    /// all x values should be unique
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use utils::rational::Rational;
+    /// use utils::sequence::Polynomial;
+    ///
+    /// let polynomial = Polynomial::interpolate(&[(65, 3955), (196, 35214), (327, 97607)]);
+    /// assert_eq!(polynomial.degree(), 2);
+    /// assert_eq!(polynomial.evaluate_int(458), Rational::from(191134));
+    ///
+    /// let half = Polynomial::interpolate(&[(0, 0), (2, 1)]);
+    /// assert_eq!(half.evaluate_int(3), Rational::new(3, 2));
+    /// ```
+    pub fn interpolate(points: &[(i64, i64)]) -> Self {
+        let mut result = vec![Rational::zero(); points.len()];
+
+        for (i, (xi, yi)) in points.iter().enumerate() {
+            let mut basis = vec![Rational::from(1)];
+            let mut denominator = Rational::from(1);
+
+            for (j, (xj, _)) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                assert_ne!(xi, xj, "All x values should be unique");
+
+                // Multiply the basis by (x - xj)
+                let mut next = vec![Rational::zero(); basis.len() + 1];
+                for (power, coefficient) in basis.iter().enumerate() {
+                    next[power + 1] = next[power + 1] + *coefficient;
+                    next[power] = next[power] - *coefficient * Rational::from(*xj);
+                }
+                basis = next;
+                denominator = denominator * Rational::from(xi - xj);
+            }
+
+            let factor = Rational::from(*yi) / denominator;
+            for (power, coefficient) in basis.iter().enumerate() {
+                result[power] = result[power] + *coefficient * factor;
+            }
+        }
+
+        Self::new(result)
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /// Returns the degree, the zero polynomial is treated as degree 0
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn evaluate(&self, x: Rational) -> Rational {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::zero(), |acc, coefficient| acc * x + *coefficient)
+    }
+
+    pub fn evaluate_int(&self, x: i64) -> Rational {
+        self.evaluate(Rational::from(x))
+    }
+
+    fn trim(&mut self) {
+        while self.coefficients.last() == Some(&Rational::zero()) {
+            self.coefficients.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extrapolate_matches_polynomial() {
+        let samples: Vec<i64> = (0..6).map(|x| 3 * x * x * x - 7 * x + 2).collect();
+        let points: Vec<(i64, i64)> = samples
+            .iter()
+            .enumerate()
+            .map(|(x, y)| (x as i64, *y))
+            .collect();
+        let polynomial = Polynomial::interpolate(&points);
+
+        assert_eq!(degree(&samples), Some(3));
+        assert_eq!(polynomial.degree(), 3);
+        for x in -20..20 {
+            assert_eq!(
+                polynomial.evaluate_int(x),
+                Rational::from(extrapolate(&samples, x))
+            );
+            assert_eq!(
+                extrapolate(&samples, x),
+                (3 * x * x * x - 7 * x + 2) as i128
+            );
+        }
+    }
+
+    #[test]
+    fn difference_table_stops_at_zero() {
+        assert_eq!(
+            difference_table(&[1, 3, 6, 10]),
+            vec![vec![1, 3, 6, 10], vec![2, 3, 4], vec![1, 1], vec![0]]
+        );
+        assert_eq!(difference_table(&[5]), vec![vec![5]]);
+    }
+}